@@ -1,26 +1,23 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 use rayon::prelude::*;
-use vortex_hash::hardware::UltraPerformance;
+use siphasher::sip::SipHasher13;
+use std::hash::{BuildHasher, Hasher};
+use vortex_hash::{hash, TreeHash, VortexBuildHasher, VortexFastBuildHasher};
+use xxhash_rust::xxh3::Xxh3;
 
 fn bench_hash_small(c: &mut Criterion) {
     let data = vec![0u8; 1024]; // 1KB
-    c.bench_function("hash_ultra_optimized_small", |b| {
-        b.iter(|| UltraPerformance::hash_ultra_optimized(black_box(&data)))
-    });
+    c.bench_function("hash_small", |b| b.iter(|| hash(black_box(&data))));
 }
 
 fn bench_hash_medium(c: &mut Criterion) {
     let data = vec![0u8; 1024 * 1024]; // 1MB
-    c.bench_function("hash_ultra_optimized_medium", |b| {
-        b.iter(|| UltraPerformance::hash_ultra_optimized(black_box(&data)))
-    });
+    c.bench_function("hash_medium", |b| b.iter(|| hash(black_box(&data))));
 }
 
 fn bench_hash_large(c: &mut Criterion) {
     let data = vec![0u8; 10 * 1024 * 1024]; // 10MB
-    c.bench_function("hash_ultra_optimized_large", |b| {
-        b.iter(|| UltraPerformance::hash_ultra_optimized(black_box(&data)))
-    });
+    c.bench_function("hash_large", |b| b.iter(|| hash(black_box(&data))));
 }
 
 fn bench_hash_parallel(c: &mut Criterion) {
@@ -28,21 +25,73 @@ fn bench_hash_parallel(c: &mut Criterion) {
     let n_threads = rayon::current_num_threads();
     let chunks: Vec<_> = (0..n_threads).map(|_| data.clone()).collect();
 
-    c.bench_function("hash_ultra_optimized_parallel", |b| {
+    c.bench_function("hash_parallel", |b| {
         b.iter(|| {
             chunks
                 .par_iter()
-                .map(|chunk| UltraPerformance::hash_ultra_optimized(black_box(chunk)))
+                .map(|chunk| hash(black_box(chunk)))
                 .collect::<Vec<_>>()
         })
     });
 }
 
+fn bench_tree_hash(c: &mut Criterion) {
+    let data = vec![0u8; 10 * 1024 * 1024]; // 10MB
+    let mut group = c.benchmark_group("tree_hash_large");
+    group.throughput(Throughput::Bytes(data.len() as u64));
+    group.sample_size(10);
+    group.bench_function("serial", |b| b.iter(|| hash(black_box(&data))));
+    group.bench_function(
+        format!("tree_{}_threads", rayon::current_num_threads()),
+        |b| b.iter(|| TreeHash::digest(black_box(&data))),
+    );
+    group.finish();
+}
+
+fn bench_std_hasher(c: &mut Criterion) {
+    let keyed = VortexBuildHasher::new();
+    let fast = VortexFastBuildHasher;
+    for (name, input) in [("u64", &[0x5au8; 8][..]), ("64b", &[0x5au8; 64][..])] {
+        let mut group = c.benchmark_group(format!("std_hasher_{}", name));
+        group.bench_function("vortex_keyed", |b| {
+            b.iter(|| {
+                let mut hasher = keyed.build_hasher();
+                hasher.write(black_box(input));
+                hasher.finish()
+            })
+        });
+        group.bench_function("vortex_fast", |b| {
+            b.iter(|| {
+                let mut hasher = fast.build_hasher();
+                hasher.write(black_box(input));
+                hasher.finish()
+            })
+        });
+        group.bench_function("siphash13", |b| {
+            b.iter(|| {
+                let mut hasher = SipHasher13::new_with_keys(1, 2);
+                hasher.write(black_box(input));
+                hasher.finish()
+            })
+        });
+        group.bench_function("xxh3", |b| {
+            b.iter(|| {
+                let mut hasher = Xxh3::new();
+                hasher.write(black_box(input));
+                hasher.finish()
+            })
+        });
+        group.finish();
+    }
+}
+
 criterion_group!(
     benches,
     bench_hash_small,
     bench_hash_medium,
     bench_hash_large,
-    bench_hash_parallel
+    bench_hash_parallel,
+    bench_tree_hash,
+    bench_std_hasher
 );
 criterion_main!(benches);
@@ -1,4 +1,4 @@
-use vortex_hash::{SecurityConfig, VortexHash};
+use vortex_hash::{SecurityConfig, VortexHash, VortexMac};
 
 fn main() {
     // Basic hash
@@ -13,14 +13,14 @@ fn main() {
         constant_time: true,
         side_channel_protection: true,
     };
-    let secure_hash = VortexHash::hash_secure(data, &config);
+    let secure_hash = VortexHash::hash_secure(data, &config).unwrap();
     println!("Secure hash: {:?}", secure_hash);
 
-    // HMAC example
+    // MAC example
     let key = b"secret_key";
     let message = b"message to sign";
-    let hmac_result = VortexHash::hmac(key, message);
-    println!("HMAC: {:?}", hmac_result);
+    let mac_result = VortexMac::mac(key, message);
+    println!("MAC: {:?}", mac_result);
 
     // Constant time equality
     use vortex_hash::constant_time::ct_eq;
@@ -44,7 +44,7 @@ mod tests {
     fn test_hmac_usage() {
         let key = b"key";
         let data = b"data";
-        let hmac_result = VortexHash::hmac(key, data);
-        assert_eq!(hmac_result.len(), 32);
+        let mac_result = VortexMac::mac(key, data);
+        assert_eq!(mac_result.len(), 32);
     }
 }
@@ -0,0 +1,234 @@
+//! Authenticated encryption with the VortexHash duplex
+//!
+//! `VortexAead` is a SpongeWrap-style AEAD on the permutation, with a
+//! 64-byte rate and a 64-byte capacity (256-bit security level):
+//!
+//! 1. **Initialize**: the state is set to `key || nonce || 0...` with the
+//!    [`domain::AEAD`] byte in its last byte, and permuted.
+//! 2. **Associated data**: absorbed rate-sized block by block and padded
+//!    with `pad10*1` and the byte `0x01`, then permuted.
+//! 3. **Message**: each plaintext block is XORed into the rate, and the
+//!    resulting rate bytes are the ciphertext; the last (possibly empty)
+//!    block is padded with the byte `0x02`, then permuted.
+//! 4. **Tag**: the first [`TAG_SIZE`] bytes of the rate.
+//!
+//! Every phase ends with exactly one padded block, so the associated data
+//! and message boundaries are unambiguous. A nonce must never be reused
+//! under the same key: two messages encrypted with the same key and nonce
+//! reveal the XOR of their first differing blocks.
+//!
+//! Decryption computes the tag before releasing anything, compares it in
+//! constant time and wipes the buffer if it does not match.
+//!
+//! # Examples
+//!
+//! ```
+//! use vortex_hash::aead::VortexAead;
+//!
+//! let cipher = VortexAead::new(&[7u8; 32]);
+//! let nonce = [1u8; 16];
+//! let mut buffer = *b"attack at dawn";
+//! let tag = cipher.encrypt_in_place_detached(&nonce, b"header", &mut buffer);
+//! cipher
+//!     .decrypt_in_place_detached(&nonce, b"header", &mut buffer, &tag)
+//!     .unwrap();
+//! assert_eq!(&buffer, b"attack at dawn");
+//! ```
+
+use crate::core::{domain, STATE_SIZE};
+use crate::permutation::Permutation;
+use crate::security::DEFAULT_ROUNDS;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// Key size in bytes.
+pub const KEY_SIZE: usize = 32;
+/// Nonce size in bytes.
+pub const NONCE_SIZE: usize = 16;
+/// Tag size in bytes.
+pub const TAG_SIZE: usize = 32;
+
+/// Bytes of input processed per permutation call.
+const RATE: usize = 64;
+
+// Padding bytes closing each phase.
+const PAD_AD: u8 = 0x01;
+const PAD_MESSAGE: u8 = 0x02;
+
+/// Duplex state of one encryption or decryption.
+struct Duplex {
+    state: [u8; STATE_SIZE],
+    permutation: Permutation,
+}
+
+impl Duplex {
+    fn new(key: &[u8; KEY_SIZE], nonce: &[u8; NONCE_SIZE]) -> Self {
+        let mut state = [0u8; STATE_SIZE];
+        state[..KEY_SIZE].copy_from_slice(key);
+        state[KEY_SIZE..KEY_SIZE + NONCE_SIZE].copy_from_slice(nonce);
+        state[STATE_SIZE - 1] = domain::AEAD;
+        let mut duplex = Self {
+            state,
+            permutation: Permutation::new(DEFAULT_ROUNDS),
+        };
+        duplex.permute();
+        duplex
+    }
+
+    fn permute(&mut self) {
+        self.permutation.permute_bytes(&mut self.state);
+    }
+
+    /// Applies `pad10*1` with `pad` after `len` bytes of the current block
+    /// and permutes.
+    fn pad(&mut self, len: usize, pad: u8) {
+        self.state[len] ^= pad;
+        self.state[RATE - 1] ^= 0x80;
+        self.permute();
+    }
+
+    fn absorb_associated_data(&mut self, data: &[u8]) {
+        let mut blocks = data.chunks_exact(RATE);
+        for block in &mut blocks {
+            xor_into(&mut self.state[..RATE], block);
+            self.permute();
+        }
+        let last = blocks.remainder();
+        xor_into(&mut self.state[..last.len()], last);
+        self.pad(last.len(), PAD_AD);
+    }
+
+    fn encrypt(&mut self, buffer: &mut [u8]) {
+        let mut blocks = buffer.chunks_exact_mut(RATE);
+        for block in &mut blocks {
+            xor_into(&mut self.state[..RATE], block);
+            block.copy_from_slice(&self.state[..RATE]);
+            self.permute();
+        }
+        let last = blocks.into_remainder();
+        xor_into(&mut self.state[..last.len()], last);
+        last.copy_from_slice(&self.state[..last.len()]);
+        self.pad(last.len(), PAD_MESSAGE);
+    }
+
+    fn decrypt(&mut self, buffer: &mut [u8]) {
+        let mut blocks = buffer.chunks_exact_mut(RATE);
+        for block in &mut blocks {
+            xor_into(block, &self.state[..RATE]);
+            xor_into(&mut self.state[..RATE], block);
+            self.permute();
+        }
+        let last = blocks.into_remainder();
+        xor_into(last, &self.state[..last.len()]);
+        xor_into(&mut self.state[..last.len()], last);
+        self.pad(last.len(), PAD_MESSAGE);
+    }
+
+    fn tag(&self) -> [u8; TAG_SIZE] {
+        self.state[..TAG_SIZE].try_into().unwrap()
+    }
+}
+
+impl Drop for Duplex {
+    fn drop(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+fn xor_into(target: &mut [u8], source: &[u8]) {
+    for (t, s) in target.iter_mut().zip(source) {
+        *t ^= s;
+    }
+}
+
+/// Sponge-based AEAD keyed with a 256-bit key; wiped when dropped.
+///
+/// The RustCrypto `aead::AeadInPlace` and `aead::Aead` traits are
+/// implemented in [`crate::ecosystem`].
+#[derive(Clone)]
+pub struct VortexAead {
+    key: [u8; KEY_SIZE],
+}
+
+impl VortexAead {
+    pub fn new(key: &[u8; KEY_SIZE]) -> Self {
+        Self { key: *key }
+    }
+
+    /// Encrypts `buffer` in place and returns the tag.
+    pub fn encrypt_in_place_detached(
+        &self,
+        nonce: &[u8; NONCE_SIZE],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> [u8; TAG_SIZE] {
+        let mut duplex = Duplex::new(&self.key, nonce);
+        duplex.absorb_associated_data(associated_data);
+        duplex.encrypt(buffer);
+        duplex.tag()
+    }
+
+    /// Decrypts `buffer` in place if `tag` is valid. On failure the buffer
+    /// is zeroed and nothing is revealed about the plaintext.
+    pub fn decrypt_in_place_detached(
+        &self,
+        nonce: &[u8; NONCE_SIZE],
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &[u8; TAG_SIZE],
+    ) -> Result<(), &'static str> {
+        let mut duplex = Duplex::new(&self.key, nonce);
+        duplex.absorb_associated_data(associated_data);
+        duplex.decrypt(buffer);
+        let mut expected = duplex.tag();
+        let valid: bool = expected.ct_eq(tag).into();
+        expected.zeroize();
+        if valid {
+            Ok(())
+        } else {
+            buffer.zeroize();
+            Err("Authentication failed")
+        }
+    }
+
+    /// Returns `ciphertext || tag`.
+    pub fn encrypt(
+        &self,
+        nonce: &[u8; NONCE_SIZE],
+        associated_data: &[u8],
+        plaintext: &[u8],
+    ) -> Vec<u8> {
+        let mut output = Vec::with_capacity(plaintext.len() + TAG_SIZE);
+        output.extend_from_slice(plaintext);
+        let tag = self.encrypt_in_place_detached(nonce, associated_data, &mut output);
+        output.extend_from_slice(&tag);
+        output
+    }
+
+    /// Opens `ciphertext || tag` produced by [`VortexAead::encrypt`].
+    pub fn decrypt(
+        &self,
+        nonce: &[u8; NONCE_SIZE],
+        associated_data: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, &'static str> {
+        if ciphertext.len() < TAG_SIZE {
+            return Err("Ciphertext too short");
+        }
+        let (body, tag) = ciphertext.split_at(ciphertext.len() - TAG_SIZE);
+        let mut plaintext = body.to_vec();
+        self.decrypt_in_place_detached(
+            nonce,
+            associated_data,
+            &mut plaintext,
+            tag.try_into().unwrap(),
+        )?;
+        Ok(plaintext)
+    }
+}
+
+impl Drop for VortexAead {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
@@ -0,0 +1,389 @@
+//! Verified streaming over the tree mode (Bao-style)
+//!
+//! [`encode`] interleaves the input with the interior chaining values of
+//! its [`crate::tree`] hash, so a receiver holding only the
+//! [`TreeHash`](crate::TreeHash) digest can check every chunk as it
+//! arrives instead of after the whole input. [`extract_slice`] cuts the
+//! nodes needed for one byte range out of an encoding, and
+//! [`SliceDecoder`] verifies such a slice against the same digest.
+//!
+//! # Encoding
+//!
+//! ```text
+//! encoding = be64(length) || node(root)
+//! node     = left CV (32) || right CV (32) || node(left) || node(right)
+//!                                               (subtree of several chunks)
+//!          | chunk bytes                        (single chunk)
+//! ```
+//!
+//! Nodes appear in pre-order, so every parent precedes the children it
+//! authenticates. A slice uses the same layout but keeps only the nodes
+//! whose subtree overlaps the requested range; a zero-length range still
+//! keeps one chunk, and a range past the end keeps the last chunk, so the
+//! length in the header is always authenticated.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::io::Read;
+//! use vortex_hash::bao::{self, Decoder, SliceDecoder};
+//!
+//! let data = vec![7u8; 100_000];
+//! let (encoded, digest) = bao::encode(&data);
+//!
+//! let mut decoded = Vec::new();
+//! Decoder::new(&encoded[..], &digest).read_to_end(&mut decoded).unwrap();
+//! assert_eq!(decoded, data);
+//!
+//! let slice = bao::extract_slice(&encoded, 50_000, 100).unwrap();
+//! let mut range = Vec::new();
+//! SliceDecoder::new(&slice[..], &digest, 50_000, 100)
+//!     .read_to_end(&mut range)
+//!     .unwrap();
+//! assert_eq!(range, &data[50_000..50_100]);
+//! ```
+
+use crate::tree::{leaf_cv, parent_cv, root_digest, CHUNK_SIZE, CV_SIZE};
+use std::io::{self, Read};
+
+/// Size of the length header.
+pub const HEADER_SIZE: usize = 8;
+
+/// Size of an encoded parent node.
+pub const PARENT_SIZE: usize = 2 * CV_SIZE;
+
+/// Number of chunks in a `length`-byte input.
+fn chunk_count(length: u64) -> u64 {
+    length.div_ceil(CHUNK_SIZE as u64).max(1)
+}
+
+/// Bytes covered by the left subtree of a subtree of `length` bytes
+/// spanning more than one chunk: the largest power-of-two number of chunks
+/// below its chunk count.
+fn left_length(length: u64) -> u64 {
+    let chunks = chunk_count(length);
+    (1u64 << (63 - (chunks - 1).leading_zeros())) * CHUNK_SIZE as u64
+}
+
+/// Encoded size of a subtree of `length` bytes, without the header.
+fn tree_size(length: u64) -> u64 {
+    let parents = (chunk_count(length) - 1).saturating_mul(PARENT_SIZE as u64);
+    length.saturating_add(parents)
+}
+
+/// Size of the encoding of a `length`-byte input.
+pub fn encoded_size(length: u64) -> u64 {
+    tree_size(length).saturating_add(HEADER_SIZE as u64)
+}
+
+/// Encodes `data`, returning the encoding and its tree digest (equal to
+/// [`TreeHash::digest`](crate::TreeHash::digest) of `data`).
+///
+/// Subtrees are hashed in parallel.
+pub fn encode(data: &[u8]) -> (Vec<u8>, [u8; 32]) {
+    let length = data.len() as u64;
+    let mut encoded = vec![0u8; encoded_size(length) as usize];
+    encoded[..HEADER_SIZE].copy_from_slice(&length.to_be_bytes());
+    let root = encode_subtree(data, 0, &mut encoded[HEADER_SIZE..]);
+    (encoded, root_digest(length, &root))
+}
+
+/// Writes the encoding of the subtree over `data`, whose first chunk has
+/// index `first`, to `out` and returns its chaining value.
+fn encode_subtree(data: &[u8], first: u64, out: &mut [u8]) -> [u8; CV_SIZE] {
+    if data.len() <= CHUNK_SIZE {
+        out.copy_from_slice(data);
+        return leaf_cv(first, data);
+    }
+    let split = left_length(data.len() as u64);
+    let (left_data, right_data) = data.split_at(split as usize);
+    let (node, children) = out.split_at_mut(PARENT_SIZE);
+    let (left_out, right_out) = children.split_at_mut(tree_size(split) as usize);
+    let right_first = first + split / CHUNK_SIZE as u64;
+    let (left, right) = rayon::join(
+        || encode_subtree(left_data, first, left_out),
+        || encode_subtree(right_data, right_first, right_out),
+    );
+    node[..CV_SIZE].copy_from_slice(&left);
+    node[CV_SIZE..].copy_from_slice(&right);
+    parent_cv(&left, &right)
+}
+
+/// Byte range requested from a slice, with the end made exclusive and at
+/// least one byte past `start`.
+#[derive(Debug, Clone, Copy)]
+struct Range {
+    start: u64,
+    end: u64,
+}
+
+impl Range {
+    fn new(start: u64, len: u64) -> Self {
+        Self {
+            start,
+            end: start.saturating_add(len.max(1)),
+        }
+    }
+
+    const ALL: Range = Range {
+        start: 0,
+        end: u64::MAX,
+    };
+
+    /// Whether the subtree of `length` bytes at byte `offset` overlaps the
+    /// range. The last subtree of an input extends to infinity.
+    fn overlaps(&self, offset: u64, length: u64, total: u64) -> bool {
+        let end = if offset + length == total {
+            u64::MAX
+        } else {
+            offset + length
+        };
+        offset < self.end && self.start < end
+    }
+}
+
+/// Cuts the slice for `len` bytes at `start` out of a full encoding.
+///
+/// The encoding itself is not verified; [`SliceDecoder`] does that.
+pub fn extract_slice(encoded: &[u8], start: u64, len: u64) -> Result<Vec<u8>, &'static str> {
+    if encoded.len() < HEADER_SIZE {
+        return Err("Invalid encoding length");
+    }
+    let length = u64::from_be_bytes(encoded[..HEADER_SIZE].try_into().unwrap());
+    if encoded.len() as u64 != encoded_size(length) {
+        return Err("Invalid encoding length");
+    }
+    let mut slice = encoded[..HEADER_SIZE].to_vec();
+    let range = Range::new(start, len);
+    slice_subtree(
+        &encoded[HEADER_SIZE..],
+        0,
+        length,
+        length,
+        range,
+        &mut slice,
+    );
+    Ok(slice)
+}
+
+/// Appends the nodes of the subtree encoded in `encoded`, covering
+/// `length` bytes at byte `offset`, that overlap `range`.
+fn slice_subtree(
+    encoded: &[u8],
+    offset: u64,
+    length: u64,
+    total: u64,
+    range: Range,
+    slice: &mut Vec<u8>,
+) {
+    if !range.overlaps(offset, length, total) {
+        return;
+    }
+    if length <= CHUNK_SIZE as u64 {
+        slice.extend_from_slice(encoded);
+        return;
+    }
+    let split = left_length(length);
+    let (node, children) = encoded.split_at(PARENT_SIZE);
+    let (left, right) = children.split_at(tree_size(split) as usize);
+    slice.extend_from_slice(node);
+    slice_subtree(left, offset, split, total, range, slice);
+    slice_subtree(right, offset + split, length - split, total, range, slice);
+}
+
+/// A subtree whose node is next in the stream.
+#[derive(Debug, Clone, Copy)]
+struct Pending {
+    /// Expected chaining value; `None` for the root, which is checked
+    /// against the digest instead.
+    cv: Option<[u8; CV_SIZE]>,
+    offset: u64,
+    length: u64,
+}
+
+/// Shared state of [`Decoder`] and [`SliceDecoder`].
+#[derive(Debug)]
+struct Verifier<R> {
+    reader: R,
+    digest: [u8; 32],
+    range: Range,
+    output_end: u64,    // End of the bytes to return, at most `range.end`
+    total: Option<u64>, // Input length, once the header has been read
+    stack: Vec<Pending>,
+    chunk: Vec<u8>,
+    pos: usize, // Next byte of `chunk` to return
+    end: usize, // End of the part of `chunk` inside the range
+}
+
+fn mismatch() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "hash mismatch")
+}
+
+fn trailing_bytes() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "trailing bytes after the encoding",
+    )
+}
+
+impl<R: Read> Verifier<R> {
+    fn new(reader: R, digest: &[u8; 32], range: Range, output_end: u64) -> Self {
+        Self {
+            reader,
+            digest: *digest,
+            range,
+            output_end,
+            total: None,
+            stack: Vec::new(),
+            chunk: Vec::with_capacity(CHUNK_SIZE),
+            pos: 0,
+            end: 0,
+        }
+    }
+
+    /// Checks the chaining value `cv` of `node`, which is the root when it
+    /// has no expected value.
+    fn check(&self, node: &Pending, cv: &[u8; CV_SIZE]) -> io::Result<()> {
+        let valid = match node.cv {
+            Some(expected) => expected == *cv,
+            None => root_digest(node.length, cv) == self.digest,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(mismatch())
+        }
+    }
+
+    /// Reads and verifies nodes up to the next chunk overlapping the range.
+    /// Returns `false` once the last node has been read and the reader is
+    /// at its end.
+    fn next_chunk(&mut self) -> io::Result<bool> {
+        let total = match self.total {
+            Some(total) => total,
+            None => {
+                let mut header = [0u8; HEADER_SIZE];
+                self.reader.read_exact(&mut header)?;
+                let total = u64::from_be_bytes(header);
+                self.total = Some(total);
+                self.stack.push(Pending {
+                    cv: None,
+                    offset: 0,
+                    length: total,
+                });
+                total
+            }
+        };
+        while let Some(node) = self.stack.pop() {
+            if !self.range.overlaps(node.offset, node.length, total) {
+                continue;
+            }
+            if node.length <= CHUNK_SIZE as u64 {
+                self.chunk.resize(node.length as usize, 0);
+                self.reader.read_exact(&mut self.chunk)?;
+                let index = node.offset / CHUNK_SIZE as u64;
+                self.check(&node, &leaf_cv(index, &self.chunk))?;
+                let start = self.range.start.saturating_sub(node.offset);
+                let end = self.output_end.saturating_sub(node.offset);
+                self.end = end.min(node.length) as usize;
+                self.pos = (start as usize).min(self.end);
+                return Ok(true);
+            }
+            let mut parent = [0u8; PARENT_SIZE];
+            self.reader.read_exact(&mut parent)?;
+            let left: [u8; CV_SIZE] = parent[..CV_SIZE].try_into().unwrap();
+            let right: [u8; CV_SIZE] = parent[CV_SIZE..].try_into().unwrap();
+            self.check(&node, &parent_cv(&left, &right))?;
+            let split = left_length(node.length);
+            self.stack.push(Pending {
+                cv: Some(right),
+                offset: node.offset + split,
+                length: node.length - split,
+            });
+            self.stack.push(Pending {
+                cv: Some(left),
+                offset: node.offset,
+                length: split,
+            });
+        }
+        self.check_end()?;
+        Ok(false)
+    }
+
+    /// Fails if the reader has bytes left after the last node.
+    fn check_end(&mut self) -> io::Result<()> {
+        let mut byte = [0u8; 1];
+        loop {
+            match self.reader.read(&mut byte) {
+                Ok(0) => return Ok(()),
+                Ok(_) => return Err(trailing_bytes()),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos == self.end {
+            if buf.is_empty() || !self.next_chunk()? {
+                return Ok(0);
+            }
+        }
+        let n = buf.len().min(self.end - self.pos);
+        buf[..n].copy_from_slice(&self.chunk[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Reads a full encoding and returns the input, verifying each chunk
+/// against the digest before returning any of its bytes.
+///
+/// A corrupted node, or bytes left after the final chunk, fail the read with
+/// [`io::ErrorKind::InvalidData`]; bytes returned before the error have
+/// already been verified.
+#[derive(Debug)]
+pub struct Decoder<R> {
+    verifier: Verifier<R>,
+}
+
+impl<R: Read> Decoder<R> {
+    pub fn new(reader: R, digest: &[u8; 32]) -> Self {
+        Self {
+            verifier: Verifier::new(reader, digest, Range::ALL, u64::MAX),
+        }
+    }
+}
+
+impl<R: Read> Read for Decoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.verifier.read(buf)
+    }
+}
+
+/// Reads a slice produced by [`extract_slice`] and returns the verified
+/// bytes of the range it was extracted for.
+///
+/// `start` and `len` must match the values passed to [`extract_slice`].
+/// A range extending past the end of the input is cut short. As with
+/// [`Decoder`], bytes after the last node of the slice are an error.
+#[derive(Debug)]
+pub struct SliceDecoder<R> {
+    verifier: Verifier<R>,
+}
+
+impl<R: Read> SliceDecoder<R> {
+    pub fn new(reader: R, digest: &[u8; 32], start: u64, len: u64) -> Self {
+        let range = Range::new(start, len);
+        let output_end = start.saturating_add(len);
+        Self {
+            verifier: Verifier::new(reader, digest, range, output_end),
+        }
+    }
+}
+
+impl<R: Read> Read for SliceDecoder<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.verifier.read(buf)
+    }
+}
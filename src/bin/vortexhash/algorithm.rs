@@ -0,0 +1,191 @@
+//! Digest algorithms selectable on the command line
+//!
+//! Every entry of [`compatibility::REGISTRY`] can be selected by its name
+//! or an alias, so old and new checksums can be produced in one pass over
+//! the data. Two VortexHash modes outside the registry are added here:
+//! `vortex-secure` (512-bit security level) and `vortex-xof` (`--length`
+//! bytes of extendable output). Tag names follow GNU `--tag` output where
+//! one exists (`SHA256`, `SHA1`, `MD5`), so those checksum files can be
+//! verified directly.
+
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use vortex_hash::compatibility::{self, UniversalHash};
+use vortex_hash::{SecurityConfig, VortexHash};
+
+/// Size of the read buffer used when hashing a file.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Output length of `vortex-xof` when `--length` is not given.
+pub const DEFAULT_XOF_LENGTH: usize = 64;
+
+/// Security level of `vortex-secure`.
+const SECURE_LEVEL: u32 = 512;
+
+/// Tag name prefix of the extendable output, followed by its length in bits.
+const XOF_TAG_PREFIX: &str = "VORTEXHASH-XOF-";
+
+/// Digest printed for each file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// An entry of the registry, computed through [`UniversalHash`].
+    Registry(compatibility::Algorithm),
+    /// [`VortexHash::finalize`] at the 512-bit security level.
+    VortexSecure,
+    /// The given number of bytes of [`VortexHash::finalize_xof`] output.
+    VortexXof(usize),
+}
+
+impl Algorithm {
+    /// The algorithm used without `--algo`: VortexHash-256.
+    pub const DEFAULT: Algorithm = Algorithm::Registry(compatibility::Algorithm::Vortex256);
+
+    /// Canonical names accepted by `--algo`, for error messages.
+    pub fn names() -> String {
+        let mut names: Vec<&str> = compatibility::Algorithm::ALL
+            .iter()
+            .map(|algorithm| algorithm.name())
+            .collect();
+        names.extend(["vortex-secure", "vortex-xof"]);
+        names.join(", ")
+    }
+
+    /// Every algorithm, in `--all` order: the registry, then the modes
+    /// outside it.
+    pub fn all(xof_length: usize) -> Vec<Algorithm> {
+        compatibility::Algorithm::ALL
+            .into_iter()
+            .map(Algorithm::Registry)
+            .chain([Algorithm::VortexSecure, Algorithm::VortexXof(xof_length)])
+            .collect()
+    }
+
+    /// Parses an `--algo` name; `vortex-xof` produces `xof_length` bytes.
+    pub fn from_name(name: &str, xof_length: usize) -> Option<Self> {
+        match name {
+            "vortex-secure" => Some(Algorithm::VortexSecure),
+            "vortex-xof" => Some(Algorithm::VortexXof(xof_length)),
+            _ => name.parse().ok().map(Algorithm::Registry),
+        }
+    }
+
+    pub fn output_size(&self) -> usize {
+        match *self {
+            Algorithm::Registry(algorithm) => algorithm.output_size(),
+            Algorithm::VortexSecure => 32,
+            Algorithm::VortexXof(length) => length,
+        }
+    }
+
+    /// `VORTEXHASH` for the default, `VORTEXHASH-<bits>` for the other
+    /// fixed-length variants and the upper-case name for the rest.
+    pub fn tag_name(&self) -> String {
+        match *self {
+            Algorithm::Registry(compatibility::Algorithm::Vortex256) => "VORTEXHASH".to_string(),
+            Algorithm::Registry(algorithm) => match algorithm.name().strip_prefix("vortex-") {
+                Some(bits) => format!("VORTEXHASH-{}", bits),
+                None => algorithm.name().to_uppercase(),
+            },
+            Algorithm::VortexSecure => "VORTEXHASH-SECURE".to_string(),
+            Algorithm::VortexXof(length) => format!("{}{}", XOF_TAG_PREFIX, length * 8),
+        }
+    }
+
+    pub fn from_tag_name(name: &str) -> Option<Self> {
+        if let Some(bits) = name.strip_prefix(XOF_TAG_PREFIX) {
+            let bits: usize = bits.parse().ok()?;
+            if bits == 0 || !bits.is_multiple_of(8) {
+                return None;
+            }
+            return Some(Algorithm::VortexXof(bits / 8));
+        }
+        Self::all(DEFAULT_XOF_LENGTH)
+            .into_iter()
+            .find(|algorithm| algorithm.tag_name() == name)
+    }
+
+    fn hasher(&self) -> Hasher {
+        match *self {
+            Algorithm::Registry(algorithm) => Hasher::Registry(UniversalHash::new(algorithm)),
+            Algorithm::VortexSecure => {
+                let config = SecurityConfig {
+                    security_level: SECURE_LEVEL,
+                    ..SecurityConfig::default()
+                };
+                Hasher::Vortex(VortexHash::new(&config).expect("valid SecurityConfig"))
+            }
+            Algorithm::VortexXof(length) => Hasher::VortexXof(VortexHash::default(), length),
+        }
+    }
+
+    pub fn hash_reader(&self, reader: impl Read) -> io::Result<Vec<u8>> {
+        let mut digests = hash_reader_all(&[*self], reader)?;
+        Ok(digests.remove(0))
+    }
+
+    /// Hashes the file at `path`, or standard input for `-`.
+    pub fn hash_path(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let mut digests = hash_path_all(&[*self], path)?;
+        Ok(digests.remove(0))
+    }
+}
+
+/// Streaming state of one [`Algorithm`].
+enum Hasher {
+    Registry(UniversalHash),
+    Vortex(VortexHash),
+    VortexXof(VortexHash, usize),
+}
+
+impl Hasher {
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Registry(hasher) => hasher.update(data),
+            Hasher::Vortex(hasher) | Hasher::VortexXof(hasher, _) => hasher.update(data),
+        }
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Registry(hasher) => hasher.finalize(),
+            Hasher::Vortex(hasher) => hasher.finalize().to_vec(),
+            Hasher::VortexXof(hasher, length) => {
+                let mut output = vec![0u8; length];
+                hasher.finalize_xof().squeeze(&mut output);
+                output
+            }
+        }
+    }
+}
+
+/// Reads `reader` once and returns its digest under each of `algorithms`.
+pub fn hash_reader_all(
+    algorithms: &[Algorithm],
+    mut reader: impl Read,
+) -> io::Result<Vec<Vec<u8>>> {
+    let mut hashers: Vec<Hasher> = algorithms.iter().map(Algorithm::hasher).collect();
+    let mut buffer = vec![0u8; BUFFER_SIZE];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(n) => {
+                for hasher in &mut hashers {
+                    hasher.update(&buffer[..n]);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(hashers.into_iter().map(Hasher::finalize).collect())
+}
+
+/// [`hash_reader_all`] over the file at `path`, or standard input for `-`.
+pub fn hash_path_all(algorithms: &[Algorithm], path: &Path) -> io::Result<Vec<Vec<u8>>> {
+    if path == Path::new("-") {
+        hash_reader_all(algorithms, io::stdin().lock())
+    } else {
+        hash_reader_all(algorithms, File::open(path)?)
+    }
+}
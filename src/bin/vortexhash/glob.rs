@@ -0,0 +1,145 @@
+//! Shell-style glob patterns for `manifest --include` and `--exclude`
+//!
+//! `*` matches any run of characters other than `/`, `?` a single such
+//! character and `[...]` one character from a class (`[a-z]`, negated with
+//! `[!...]`). A `**` component matches any number of directories. A pattern
+//! without a `/` is matched against the last path component only, so
+//! `*.rs` selects Rust files at any depth and `target` any directory named
+//! `target`.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    AnyChar,
+    Star,
+    /// `**/`, or a trailing `**`: zero or more whole components.
+    AnyComponents,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+/// A compiled glob pattern.
+#[derive(Debug, Clone)]
+pub struct Glob {
+    tokens: Vec<Token>,
+    name_only: bool,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Result<Self, String> {
+        if pattern.is_empty() {
+            return Err("empty glob pattern".to_string());
+        }
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '*' if chars.get(i + 1) == Some(&'*')
+                    && (i == 0 || chars[i - 1] == '/')
+                    && matches!(chars.get(i + 2), None | Some('/')) =>
+                {
+                    tokens.push(Token::AnyComponents);
+                    i += 3;
+                }
+                '*' => {
+                    if tokens.last() != Some(&Token::Star) {
+                        tokens.push(Token::Star);
+                    }
+                    i += 1;
+                }
+                '?' => {
+                    tokens.push(Token::AnyChar);
+                    i += 1;
+                }
+                '[' => {
+                    let (class, next) = parse_class(&chars, i + 1)
+                        .ok_or_else(|| format!("unterminated character class in '{}'", pattern))?;
+                    tokens.push(class);
+                    i = next;
+                }
+                c => {
+                    tokens.push(Token::Literal(c));
+                    i += 1;
+                }
+            }
+        }
+        Ok(Self {
+            tokens,
+            name_only: !pattern.contains('/'),
+        })
+    }
+
+    /// Whether `path`, relative and `/`-separated, matches the pattern.
+    pub fn matches(&self, path: &str) -> bool {
+        let path = if self.name_only {
+            path.rsplit('/').next().unwrap_or(path)
+        } else {
+            path
+        };
+        let text: Vec<char> = path.chars().collect();
+        match_tokens(&self.tokens, &text)
+    }
+}
+
+/// Parses the class starting after `[` at `start`; returns the token and
+/// the index after the closing `]`.
+fn parse_class(chars: &[char], start: usize) -> Option<(Token, usize)> {
+    let mut i = start;
+    let negated = matches!(chars.get(i), Some('!') | Some('^'));
+    if negated {
+        i += 1;
+    }
+    let mut ranges = Vec::new();
+    let mut first = true;
+    loop {
+        let c = *chars.get(i)?;
+        if c == ']' && !first {
+            return Some((Token::Class { negated, ranges }, i + 1));
+        }
+        first = false;
+        if chars.get(i + 1) == Some(&'-') && chars.get(i + 2).is_some_and(|&end| end != ']') {
+            ranges.push((c, chars[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((c, c));
+            i += 1;
+        }
+    }
+}
+
+fn match_tokens(tokens: &[Token], text: &[char]) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return text.is_empty();
+    };
+    match token {
+        Token::Literal(c) => text.first() == Some(c) && match_tokens(rest, &text[1..]),
+        Token::AnyChar => text.first().is_some_and(|&c| c != '/') && match_tokens(rest, &text[1..]),
+        Token::Class { negated, ranges } => text.first().is_some_and(|&c| {
+            c != '/'
+                && ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != *negated
+                && match_tokens(rest, &text[1..])
+        }),
+        Token::Star => {
+            for skip in 0..=text.len() {
+                if match_tokens(rest, &text[skip..]) {
+                    return true;
+                }
+                if text.get(skip) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Token::AnyComponents => {
+            if rest.is_empty() {
+                return true;
+            }
+            (0..=text.len())
+                .filter(|&skip| skip == 0 || text[skip - 1] == '/')
+                .any(|skip| match_tokens(rest, &text[skip..]))
+        }
+    }
+}
@@ -0,0 +1,471 @@
+//! `vortexhash`: print or check VortexHash checksums
+//!
+//! Legacy digests can be computed alongside VortexHash in the same pass
+//! over each file; see [`algorithm`].
+//!
+//! Output and checksum files follow the `sha256sum`/`b3sum` conventions:
+//! `<hex digest>  <path>` per line, or `ALGORITHM (<path>) = <hex digest>`
+//! with `--tag`. Paths containing a backslash or a newline are escaped and
+//! the line is prefixed with a backslash.
+
+mod algorithm;
+mod glob;
+mod manifest;
+
+use algorithm::{hash_path_all, Algorithm, DEFAULT_XOF_LENGTH};
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::ExitCode;
+use vortex_hash::to_hex;
+
+const USAGE: &str = "\
+Usage: vortexhash [OPTION]... [FILE]...
+  or:  vortexhash manifest [OPTION]... [DIR]
+Print or check VortexHash checksums, or fingerprint a directory tree (see
+'vortexhash manifest --help').
+With no FILE, or when FILE is -, read standard input.
+
+  -a, --algo <NAME>   digest algorithm, repeatable or comma-separated:
+                      vortex (default), vortex-224/256/384/512,
+                      vortex-secure, vortex-xof, sha224, sha256, sha384,
+                      sha512, sha1, md5, xxh3 or siphash
+      --all           compute every algorithm at once
+  -c, --check         read checksums from the FILEs and check them
+  -l, --length <N>    output N bytes of vortex-xof output (default 64);
+                      without --algo, selects vortex-xof
+      --tag           create a BSD-style checksum, the default with more
+                      than one algorithm
+  -q, --quiet         don't print OK for each successfully verified file
+  -h, --help          display this help and exit
+  -V, --version       output version information and exit
+
+Exit status is 0 if all files were hashed or verified, 1 if a file could
+not be read or a checksum did not match, and 2 on invalid usage.";
+
+#[derive(Debug)]
+struct Options {
+    check: bool,
+    tag: bool,
+    quiet: bool,
+    algorithms: Vec<Algorithm>,
+    files: Vec<OsString>,
+}
+
+/// Result of parsing the command line.
+enum Command {
+    Hash(Options),
+    Manifest(manifest::Options),
+    Help(&'static str),
+    Version,
+}
+
+/// Splits `--flag=value` into the flag and its inline value.
+fn split_flag(text: &str) -> (&str, Option<String>) {
+    match text.split_once('=') {
+        Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+        _ => (text, None),
+    }
+}
+
+/// Value of `flag`, given inline or as the next argument.
+fn option_value(
+    flag: &str,
+    inline_value: Option<String>,
+    args: &mut impl Iterator<Item = OsString>,
+) -> Result<String, String> {
+    match inline_value {
+        Some(value) => Ok(value),
+        None => args
+            .next()
+            .and_then(|value| value.into_string().ok())
+            .ok_or_else(|| format!("option '{}' requires an argument", flag)),
+    }
+}
+
+/// `--algo`, `--all` and `--length` as given on the command line.
+#[derive(Debug, Default)]
+struct AlgorithmArgs {
+    names: Vec<String>,
+    all: bool,
+    length: Option<usize>,
+}
+
+impl AlgorithmArgs {
+    /// Options that take a value.
+    const VALUE_FLAGS: [&'static str; 2] = ["--algo", "--length"];
+
+    /// Consumes `flag` if it selects algorithms; returns whether it did.
+    fn parse(
+        &mut self,
+        flag: &str,
+        inline_value: Option<String>,
+        args: &mut impl Iterator<Item = OsString>,
+    ) -> Result<bool, String> {
+        match flag {
+            "-a" | "--algo" => {
+                let value = option_value(flag, inline_value, args)?;
+                self.names.extend(value.split(',').map(str::to_string));
+            }
+            "--all" => self.all = true,
+            "-l" | "--length" => {
+                let value = option_value(flag, inline_value, args)?;
+                let length = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&length| length > 0)
+                    .ok_or_else(|| format!("invalid length '{}'", value))?;
+                self.length = Some(length);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// The selected algorithms, in command-line order without duplicates.
+    fn resolve(self) -> Result<Vec<Algorithm>, String> {
+        let xof_length = self.length.unwrap_or(DEFAULT_XOF_LENGTH);
+        if self.all {
+            if !self.names.is_empty() {
+                return Err("--all cannot be combined with --algo".to_string());
+            }
+            return Ok(Algorithm::all(xof_length));
+        }
+        if self.names.is_empty() {
+            return Ok(vec![match self.length {
+                Some(length) => Algorithm::VortexXof(length),
+                None => Algorithm::DEFAULT,
+            }]);
+        }
+        let mut algorithms = Vec::new();
+        for name in &self.names {
+            let algorithm = Algorithm::from_name(name, xof_length).ok_or_else(|| {
+                format!(
+                    "unknown algorithm '{}' (expected one of: {})",
+                    name,
+                    Algorithm::names()
+                )
+            })?;
+            if !algorithms.contains(&algorithm) {
+                algorithms.push(algorithm);
+            }
+        }
+        if self.length.is_some() && !algorithms.contains(&Algorithm::VortexXof(xof_length)) {
+            return Err("--length only applies to vortex-xof".to_string());
+        }
+        Ok(algorithms)
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = OsString>) -> Result<Command, String> {
+    let mut args = args.into_iter().peekable();
+    if args.peek().is_some_and(|arg| arg == "manifest") {
+        args.next();
+        return manifest::parse_args(args);
+    }
+    let mut options = Options {
+        check: false,
+        tag: false,
+        quiet: false,
+        algorithms: Vec::new(),
+        files: Vec::new(),
+    };
+    let mut selection = AlgorithmArgs::default();
+    while let Some(arg) = args.next() {
+        let Some(text) = arg.to_str() else {
+            options.files.push(arg);
+            continue;
+        };
+        if text == "--" {
+            options.files.extend(args);
+            break;
+        }
+        if text == "-" || !text.starts_with('-') {
+            options.files.push(arg);
+            continue;
+        }
+        let (flag, inline_value) = split_flag(text);
+        if inline_value.is_some() && !AlgorithmArgs::VALUE_FLAGS.contains(&flag) {
+            return Err(format!("option '{}' doesn't allow an argument", flag));
+        }
+        if selection.parse(flag, inline_value.clone(), &mut args)? {
+            continue;
+        }
+        match flag {
+            "-c" | "--check" => options.check = true,
+            "--tag" => options.tag = true,
+            "-q" | "--quiet" => options.quiet = true,
+            "-h" | "--help" => return Ok(Command::Help(USAGE)),
+            "-V" | "--version" => return Ok(Command::Version),
+            _ => return Err(format!("unrecognized option '{}'", text)),
+        }
+    }
+    options.algorithms = selection.resolve()?;
+    if options.check && options.tag {
+        return Err("the --tag option is meaningless when verifying checksums".to_string());
+    }
+    if options.check && options.algorithms.len() > 1 {
+        return Err("only one algorithm can be selected when verifying checksums".to_string());
+    }
+    if options.files.is_empty() {
+        options.files.push(OsString::from("-"));
+    }
+    Ok(Command::Hash(options))
+}
+
+/// Escapes backslashes and newlines in `name`; returns whether anything
+/// was escaped.
+fn escape_name(name: &str) -> (String, bool) {
+    if !name.contains(['\\', '\n', '\r']) {
+        return (name.to_string(), false);
+    }
+    let mut escaped = String::with_capacity(name.len() + 2);
+    for c in name.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    (escaped, true)
+}
+
+fn unescape_name(name: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '\\' => unescaped.push('\\'),
+            'n' => unescaped.push('\n'),
+            'r' => unescaped.push('\r'),
+            _ => return None,
+        }
+    }
+    Some(unescaped)
+}
+
+/// Formats one checksum line, without the trailing newline.
+fn format_line(algorithm: Algorithm, digest: &[u8], name: &str, tag: bool) -> String {
+    let (name, escaped) = escape_name(name);
+    let prefix = if escaped { "\\" } else { "" };
+    if tag {
+        format!(
+            "{}{} ({}) = {}",
+            prefix,
+            algorithm.tag_name(),
+            name,
+            to_hex(digest)
+        )
+    } else {
+        format!("{}{}  {}", prefix, to_hex(digest), name)
+    }
+}
+
+/// A parsed line of a checksum file.
+#[derive(Debug, PartialEq, Eq)]
+struct ChecksumLine {
+    algorithm: Algorithm,
+    digest: String,
+    name: String,
+}
+
+/// Parses a `--tag` or untagged checksum line. Untagged lines carry no
+/// algorithm, so they are read as `default`.
+fn parse_line(line: &str, default: Algorithm) -> Option<ChecksumLine> {
+    let (escaped, line) = match line.strip_prefix('\\') {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+    let (algorithm, digest, name) = match line.split_once(" (") {
+        Some((tag_name, rest)) if !tag_name.contains(' ') && line.contains(") = ") => {
+            let (name, digest) = rest.rsplit_once(") = ")?;
+            (Algorithm::from_tag_name(tag_name)?, digest, name)
+        }
+        _ => {
+            let (digest, rest) = line.split_once(' ')?;
+            let name = rest.strip_prefix([' ', '*'])?;
+            (default, digest, name)
+        }
+    };
+    if digest.len() != algorithm.output_size() * 2
+        || !digest.bytes().all(|b| b.is_ascii_hexdigit())
+        || name.is_empty()
+    {
+        return None;
+    }
+    let name = if escaped {
+        unescape_name(name)?
+    } else {
+        name.to_string()
+    };
+    Some(ChecksumLine {
+        algorithm,
+        digest: digest.to_ascii_lowercase(),
+        name,
+    })
+}
+
+/// Prints the checksum of each file. Returns `false` if any file could
+/// not be read.
+fn print_checksums(options: &Options, out: &mut impl Write) -> io::Result<bool> {
+    let tag = options.tag || options.algorithms.len() > 1;
+    let mut success = true;
+    for file in &options.files {
+        let path = Path::new(file);
+        match hash_path_all(&options.algorithms, path) {
+            Ok(digests) => {
+                let name = path.to_string_lossy();
+                for (algorithm, digest) in options.algorithms.iter().zip(digests) {
+                    writeln!(out, "{}", format_line(*algorithm, &digest, &name, tag))?;
+                }
+            }
+            Err(e) => {
+                out.flush()?;
+                eprintln!("vortexhash: {}: {}", path.display(), e);
+                success = false;
+            }
+        }
+    }
+    Ok(success)
+}
+
+/// Counters reported after checking all checksum files.
+#[derive(Debug, Default)]
+struct CheckSummary {
+    malformed: usize,
+    unreadable: usize,
+    mismatched: usize,
+}
+
+fn plural(count: usize, singular: &str, plural: &str) -> String {
+    format!("{} {}", count, if count == 1 { singular } else { plural })
+}
+
+/// Verifies the lines of one checksum file. Returns `false` if the file
+/// could not be read or has no checksum lines.
+fn check_file(
+    file: &Path,
+    options: &Options,
+    summary: &mut CheckSummary,
+    out: &mut impl Write,
+) -> io::Result<bool> {
+    // Read the whole list first: its entries may name standard input too.
+    let mut contents = String::new();
+    let read = if file == Path::new("-") {
+        io::stdin().lock().read_to_string(&mut contents)
+    } else {
+        File::open(file).and_then(|mut f| f.read_to_string(&mut contents))
+    };
+    if let Err(e) = read {
+        out.flush()?;
+        eprintln!("vortexhash: {}: {}", file.display(), e);
+        return Ok(false);
+    }
+    let mut checked = 0;
+    for line in contents.lines() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some(entry) = parse_line(line, options.algorithms[0]) else {
+            summary.malformed += 1;
+            continue;
+        };
+        checked += 1;
+        let (shown, escaped) = escape_name(&entry.name);
+        let prefix = if escaped { "\\" } else { "" };
+        match entry.algorithm.hash_path(Path::new(&entry.name)) {
+            Ok(digest) if to_hex(&digest) == entry.digest => {
+                if !options.quiet {
+                    writeln!(out, "{}{}: OK", prefix, shown)?;
+                }
+            }
+            Ok(_) => {
+                summary.mismatched += 1;
+                writeln!(out, "{}{}: FAILED", prefix, shown)?;
+            }
+            Err(e) => {
+                summary.unreadable += 1;
+                out.flush()?;
+                eprintln!("vortexhash: {}: {}", entry.name, e);
+                writeln!(out, "{}{}: FAILED open or read", prefix, shown)?;
+            }
+        }
+    }
+    if checked == 0 {
+        out.flush()?;
+        eprintln!(
+            "vortexhash: {}: no properly formatted checksum lines found",
+            file.display()
+        );
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Verifies every checksum file. Returns `false` on any mismatch or
+/// unreadable file.
+fn check_checksums(options: &Options, out: &mut impl Write) -> io::Result<bool> {
+    let mut summary = CheckSummary::default();
+    let mut success = true;
+    for file in &options.files {
+        success &= check_file(Path::new(file), options, &mut summary, out)?;
+    }
+    out.flush()?;
+    if summary.malformed > 0 {
+        eprintln!(
+            "vortexhash: WARNING: {} improperly formatted",
+            plural(summary.malformed, "line is", "lines are")
+        );
+    }
+    if summary.unreadable > 0 {
+        eprintln!(
+            "vortexhash: WARNING: {} could not be read",
+            plural(summary.unreadable, "listed file", "listed files")
+        );
+    }
+    if summary.mismatched > 0 {
+        eprintln!(
+            "vortexhash: WARNING: {} did NOT match",
+            plural(
+                summary.mismatched,
+                "computed checksum",
+                "computed checksums"
+            )
+        );
+    }
+    Ok(success && summary.unreadable == 0 && summary.mismatched == 0)
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(std::env::args_os().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("vortexhash: {}", message);
+            eprintln!("Try 'vortexhash --help' for more information.");
+            return ExitCode::from(2);
+        }
+    };
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let result = match command {
+        Command::Hash(options) if options.check => check_checksums(&options, &mut out),
+        Command::Hash(options) => print_checksums(&options, &mut out),
+        Command::Manifest(options) => manifest::run(&options, &mut out),
+        Command::Help(usage) => writeln!(out, "{}", usage).map(|_| true),
+        Command::Version => writeln!(out, "vortexhash {}", env!("CARGO_PKG_VERSION")).map(|_| true),
+    };
+    match result.and_then(|success| out.flush().map(|_| success)) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("vortexhash: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
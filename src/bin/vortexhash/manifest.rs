@@ -0,0 +1,328 @@
+//! `vortexhash manifest`: fingerprints of a directory tree
+//!
+//! The tree is walked without following symbolic links, and every regular
+//! file that passes the `--include`/`--exclude` globs is hashed in parallel.
+//! Entries are sorted by their `/`-separated path relative to the root
+//! directory.
+//!
+//! The root digest is the VortexHash of the checksum list of the entries,
+//! exactly as `vortexhash` prints it (`<digest>  <path>\n` per file, in
+//! manifest order). It depends only on the paths and contents, not on
+//! sizes or modification times, so two copies of a tree have the same root.
+
+use crate::algorithm::Algorithm;
+use crate::glob::Glob;
+use crate::{escape_name, format_line, option_value, split_flag, AlgorithmArgs, Command};
+use rayon::prelude::*;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use vortex_hash::{to_hex, VortexHash};
+
+pub const USAGE: &str = "\
+Usage: vortexhash manifest [OPTION]... [DIR]
+Hash every file below DIR (default: the current directory) in parallel
+and print a manifest with the path, size, modification time and digest of
+each file, and a root digest over the whole tree.
+
+  -a, --algo <NAME>      digest algorithm (see 'vortexhash --help')
+  -l, --length <N>       output N bytes of vortex-xof output per file
+      --include <GLOB>   only hash files matching GLOB (repeatable)
+      --exclude <GLOB>   skip files and directories matching GLOB
+                         (repeatable)
+      --format <FORMAT>  text (default), json or csv
+      --root             print only the root digest
+  -h, --help             display this help and exit
+
+A GLOB without '/' matches file names at any depth; otherwise it matches
+the path relative to DIR, where '**' stands for any number of directories.
+The root digest only depends on paths and file contents.";
+
+/// Manifest output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Debug)]
+pub struct Options {
+    algorithm: Algorithm,
+    include: Vec<Glob>,
+    exclude: Vec<Glob>,
+    format: Format,
+    root_only: bool,
+    dir: PathBuf,
+}
+
+pub fn parse_args(args: impl IntoIterator<Item = OsString>) -> Result<Command, String> {
+    let mut options = Options {
+        algorithm: Algorithm::DEFAULT,
+        include: Vec::new(),
+        exclude: Vec::new(),
+        format: Format::Text,
+        root_only: false,
+        dir: PathBuf::from("."),
+    };
+    let mut selection = AlgorithmArgs::default();
+    let mut dirs = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let Some(text) = arg.to_str() else {
+            dirs.push(arg);
+            continue;
+        };
+        if text == "--" {
+            dirs.extend(args);
+            break;
+        }
+        if !text.starts_with('-') {
+            dirs.push(arg);
+            continue;
+        }
+        let (flag, inline_value) = split_flag(text);
+        let takes_value = AlgorithmArgs::VALUE_FLAGS.contains(&flag)
+            || matches!(flag, "--include" | "--exclude" | "--format");
+        if inline_value.is_some() && !takes_value {
+            return Err(format!("option '{}' doesn't allow an argument", flag));
+        }
+        if selection.parse(flag, inline_value.clone(), &mut args)? {
+            continue;
+        }
+        match flag {
+            "-h" | "--help" => return Ok(Command::Help(USAGE)),
+            "--root" => options.root_only = true,
+            "--include" => {
+                let pattern = option_value(flag, inline_value, &mut args)?;
+                options.include.push(Glob::new(&pattern)?);
+            }
+            "--exclude" => {
+                let pattern = option_value(flag, inline_value, &mut args)?;
+                options.exclude.push(Glob::new(&pattern)?);
+            }
+            "--format" => {
+                options.format = match option_value(flag, inline_value, &mut args)?.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    other => return Err(format!("invalid format '{}'", other)),
+                };
+            }
+            _ => return Err(format!("unrecognized option '{}'", text)),
+        }
+    }
+    options.algorithm = match selection.resolve()?[..] {
+        [algorithm] => algorithm,
+        _ => return Err("manifest takes a single algorithm".to_string()),
+    };
+    match dirs.len() {
+        0 => {}
+        1 => options.dir = PathBuf::from(dirs.remove(0)),
+        _ => return Err("manifest takes a single directory".to_string()),
+    }
+    Ok(Command::Manifest(options))
+}
+
+/// One file of the manifest.
+#[derive(Debug)]
+struct Entry {
+    path: String,
+    size: u64,
+    /// Seconds since the Unix epoch.
+    mtime: i64,
+    digest: Vec<u8>,
+}
+
+/// Collects the regular files below `dir`, as `(relative path, path)`
+/// pairs. Returns `false` if a directory could not be read.
+fn walk(dir: &Path, prefix: &str, options: &Options, files: &mut Vec<(String, PathBuf)>) -> bool {
+    let entries =
+        match fs::read_dir(dir).and_then(|entries| entries.collect::<io::Result<Vec<_>>>()) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("vortexhash: {}: {}", dir.display(), e);
+                return false;
+            }
+        };
+    let mut success = true;
+    for entry in entries {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        let relative = if prefix.is_empty() {
+            name.into_owned()
+        } else {
+            format!("{}/{}", prefix, name)
+        };
+        if options.exclude.iter().any(|glob| glob.matches(&relative)) {
+            continue;
+        }
+        let file_type = match entry.file_type() {
+            Ok(file_type) => file_type,
+            Err(e) => {
+                eprintln!("vortexhash: {}: {}", entry.path().display(), e);
+                success = false;
+                continue;
+            }
+        };
+        if file_type.is_dir() {
+            success &= walk(&entry.path(), &relative, options, files);
+        } else if file_type.is_file()
+            && (options.include.is_empty()
+                || options.include.iter().any(|glob| glob.matches(&relative)))
+        {
+            files.push((relative, entry.path()));
+        }
+    }
+    success
+}
+
+fn hash_file(relative: &str, path: &Path, algorithm: Algorithm) -> io::Result<Entry> {
+    let file = File::open(path)?;
+    let metadata = file.metadata()?;
+    let mtime = match metadata.modified()?.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs() as i64,
+        Err(before) => -(before.duration().as_secs() as i64),
+    };
+    Ok(Entry {
+        path: relative.to_string(),
+        size: metadata.len(),
+        mtime,
+        digest: algorithm.hash_reader(&file)?,
+    })
+}
+
+/// VortexHash of the checksum list of `entries`; see the module docs.
+fn root_digest(entries: &[Entry], algorithm: Algorithm) -> [u8; 32] {
+    let mut hasher = VortexHash::default();
+    for entry in entries {
+        hasher.update(format_line(algorithm, &entry.digest, &entry.path, false).as_bytes());
+        hasher.update(b"\n");
+    }
+    hasher.finalize()
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn write_manifest(
+    entries: &[Entry],
+    root: &[u8; 32],
+    options: &Options,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    match options.format {
+        Format::Text => {
+            for entry in entries {
+                let (path, escaped) = escape_name(&entry.path);
+                writeln!(
+                    out,
+                    "{}{}  {}  {}  {}",
+                    if escaped { "\\" } else { "" },
+                    to_hex(&entry.digest),
+                    entry.size,
+                    entry.mtime,
+                    path
+                )?;
+            }
+            writeln!(out, "root: {}", to_hex(root))
+        }
+        Format::Json => {
+            writeln!(out, "{{")?;
+            writeln!(
+                out,
+                "  \"algorithm\": {},",
+                json_string(&options.algorithm.tag_name())
+            )?;
+            writeln!(out, "  \"root\": \"{}\",", to_hex(root))?;
+            write!(out, "  \"files\": [")?;
+            for (i, entry) in entries.iter().enumerate() {
+                let separator = if i == 0 { "\n" } else { ",\n" };
+                write!(
+                    out,
+                    "{}    {{\"path\": {}, \"size\": {}, \"mtime\": {}, \"digest\": \"{}\"}}",
+                    separator,
+                    json_string(&entry.path),
+                    entry.size,
+                    entry.mtime,
+                    to_hex(&entry.digest)
+                )?;
+            }
+            if !entries.is_empty() {
+                write!(out, "\n  ")?;
+            }
+            writeln!(out, "]")?;
+            writeln!(out, "}}")
+        }
+        Format::Csv => {
+            writeln!(out, "path,size,mtime,digest")?;
+            for entry in entries {
+                writeln!(
+                    out,
+                    "{},{},{},{}",
+                    csv_field(&entry.path),
+                    entry.size,
+                    entry.mtime,
+                    to_hex(&entry.digest)
+                )?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Prints the manifest of `options.dir`. Returns `false` if any file or
+/// directory could not be read; those are left out of the manifest.
+pub fn run(options: &Options, out: &mut impl Write) -> io::Result<bool> {
+    let mut files = Vec::new();
+    let mut success = walk(&options.dir, "", options, &mut files);
+    files.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+    let results: Vec<io::Result<Entry>> = files
+        .par_iter()
+        .map(|(relative, path)| hash_file(relative, path, options.algorithm))
+        .collect();
+    let mut entries = Vec::with_capacity(results.len());
+    for (result, (_, path)) in results.into_iter().zip(&files) {
+        match result {
+            Ok(entry) => entries.push(entry),
+            Err(e) => {
+                eprintln!("vortexhash: {}: {}", path.display(), e);
+                success = false;
+            }
+        }
+    }
+
+    let root = root_digest(&entries, options.algorithm);
+    if options.root_only {
+        writeln!(out, "{}  {}", to_hex(&root), options.dir.display())?;
+    } else {
+        write_manifest(&entries, &root, options, out)?;
+    }
+    Ok(success)
+}
@@ -0,0 +1,222 @@
+//! Content-defined chunking for deduplication
+//!
+//! Chunk boundaries are found with the FastCDC algorithm: a gear rolling
+//! hash `h = (h << 1) + GEAR[byte]` is updated for every byte after the
+//! first `min_size` bytes of a chunk, and a boundary is placed after the
+//! first byte where the top bits of `h` are all zero. Before `avg_size`
+//! the test uses two more bits than `log2(avg_size)` and after it two
+//! fewer, which pulls chunk sizes towards the average; chunks are cut at
+//! `max_size` regardless.
+//!
+//! The hash only depends on the last 64 bytes, so boundaries follow the
+//! content: inserting or removing bytes changes the chunks around the edit
+//! and leaves the ones after the next unchanged boundary identical. Each
+//! chunk is fingerprinted with [`crate::hash`].
+//!
+//! # Examples
+//!
+//! ```
+//! use vortex_hash::chunking::{Chunker, ChunkerParams};
+//!
+//! let data = vec![0x42u8; 100_000];
+//! let mut total = 0;
+//! for chunk in Chunker::new(&data[..], ChunkerParams::default()).unwrap() {
+//!     let chunk = chunk.unwrap();
+//!     assert_eq!(chunk.offset, total);
+//!     total += chunk.data.len() as u64;
+//! }
+//! assert_eq!(total, data.len() as u64);
+//! ```
+
+use std::io::{self, Read};
+
+/// Gear table: 256 pseudo-random words from SplitMix64 seeded with the
+/// ASCII bytes of `"VortexCD"`.
+pub const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state = u64::from_be_bytes(*b"VortexCD");
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Chunk size limits in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkerParams {
+    pub min_size: usize,
+    pub avg_size: usize,
+    pub max_size: usize,
+}
+
+impl ChunkerParams {
+    /// Minimum chunk size used by [`ChunkerParams::default`].
+    pub const DEFAULT_MIN_SIZE: usize = 2048;
+    /// Average chunk size used by [`ChunkerParams::default`].
+    pub const DEFAULT_AVG_SIZE: usize = 8192;
+    /// Maximum chunk size used by [`ChunkerParams::default`].
+    pub const DEFAULT_MAX_SIZE: usize = 65536;
+
+    /// Smallest accepted `min_size`.
+    pub const MIN_SIZE_LIMIT: usize = 64;
+    /// Largest accepted `max_size`.
+    pub const MAX_SIZE_LIMIT: usize = 1 << 28;
+
+    /// Validated parameters; see [`ChunkerParams::validate`].
+    pub fn new(min_size: usize, avg_size: usize, max_size: usize) -> Result<Self, &'static str> {
+        let params = Self {
+            min_size,
+            avg_size,
+            max_size,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Checks that `avg_size` is a power of two, that
+    /// `min_size < avg_size < max_size`, and that the sizes lie between
+    /// [`Self::MIN_SIZE_LIMIT`] and [`Self::MAX_SIZE_LIMIT`].
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if !self.avg_size.is_power_of_two() {
+            return Err("Average chunk size must be a power of two");
+        }
+        if !(self.min_size < self.avg_size && self.avg_size < self.max_size) {
+            return Err("Chunk sizes must satisfy min < avg < max");
+        }
+        if self.min_size < Self::MIN_SIZE_LIMIT || self.max_size > Self::MAX_SIZE_LIMIT {
+            return Err("Chunk sizes out of range");
+        }
+        Ok(())
+    }
+
+    /// Boundary masks before and after `avg_size`.
+    fn masks(&self) -> (u64, u64) {
+        let bits = self.avg_size.trailing_zeros();
+        (top_bits(bits + 2), top_bits(bits - 2))
+    }
+}
+
+impl Default for ChunkerParams {
+    fn default() -> Self {
+        Self {
+            min_size: Self::DEFAULT_MIN_SIZE,
+            avg_size: Self::DEFAULT_AVG_SIZE,
+            max_size: Self::DEFAULT_MAX_SIZE,
+        }
+    }
+}
+
+fn top_bits(bits: u32) -> u64 {
+    !(u64::MAX >> bits)
+}
+
+/// Length of the chunk at the start of `data`, which holds either at least
+/// `max_size` bytes or the rest of the input.
+fn cut_point(data: &[u8], params: &ChunkerParams, masks: (u64, u64)) -> usize {
+    if data.len() <= params.min_size {
+        return data.len();
+    }
+    let end = data.len().min(params.max_size);
+    let normal = end.min(params.avg_size);
+    let (mask_small, mask_large) = masks;
+    let mut hash = 0u64;
+    for (i, &byte) in data.iter().enumerate().take(end).skip(params.min_size) {
+        hash = (hash << 1).wrapping_add(GEAR[byte as usize]);
+        let mask = if i < normal { mask_small } else { mask_large };
+        if hash & mask == 0 {
+            return i + 1;
+        }
+    }
+    end
+}
+
+/// A content-defined chunk and its fingerprint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// Position of the chunk in the input.
+    pub offset: u64,
+    pub data: Vec<u8>,
+    /// [`crate::hash`] of `data`.
+    pub fingerprint: [u8; 32],
+}
+
+/// Iterator splitting a reader into content-defined [`Chunk`]s.
+///
+/// Reads ahead at most `max_size` bytes. A read error is returned once and
+/// ends the iteration.
+#[derive(Debug)]
+pub struct Chunker<R> {
+    reader: R,
+    params: ChunkerParams,
+    masks: (u64, u64),
+    buffer: Vec<u8>,
+    offset: u64,
+    eof: bool,
+}
+
+impl<R: Read> Chunker<R> {
+    /// Returns an error if `params` does not pass
+    /// [`ChunkerParams::validate`].
+    pub fn new(reader: R, params: ChunkerParams) -> Result<Self, &'static str> {
+        params.validate()?;
+        Ok(Self {
+            reader,
+            params,
+            masks: params.masks(),
+            buffer: Vec::with_capacity(params.max_size),
+            offset: 0,
+            eof: false,
+        })
+    }
+
+    /// Reads until the buffer holds `max_size` bytes or the input ends.
+    fn fill(&mut self) -> io::Result<()> {
+        let mut filled = self.buffer.len();
+        self.buffer.resize(self.params.max_size, 0);
+        while !self.eof && filled < self.buffer.len() {
+            match self.reader.read(&mut self.buffer[filled..]) {
+                Ok(0) => self.eof = true,
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.buffer.truncate(filled);
+                    self.eof = true;
+                    return Err(e);
+                }
+            }
+        }
+        self.buffer.truncate(filled);
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for Chunker<R> {
+    type Item = io::Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Err(e) = self.fill() {
+            self.buffer.clear();
+            return Some(Err(e));
+        }
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let length = cut_point(&self.buffer, &self.params, self.masks);
+        let data: Vec<u8> = self.buffer.drain(..length).collect();
+        let chunk = Chunk {
+            offset: self.offset,
+            fingerprint: crate::hash(&data),
+            data,
+        };
+        self.offset += length as u64;
+        Some(Ok(chunk))
+    }
+}
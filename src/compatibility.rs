@@ -1,10 +1,501 @@
 //! Compatibility layer for VortexHash
+//!
+//! [`UniversalHash`] computes any digest the crate links, selected at run
+//! time by an [`Algorithm`] or its name, so application code can move from
+//! a legacy hash to VortexHash by changing configuration. Each algorithm
+//! has an entry in [`REGISTRY`] with its name, output size, ASN.1 OID
+//! (where one is assigned) and multicodec code, and a constructor for a
+//! boxed [`DynHasher`].
+//!
+//! VortexHash and SipHash have no registered multicodec; they use codes
+//! from the private-use range starting at `0x300000`. XXH3 digests are
+//! big-endian and SipHash digests little-endian, matching `xxhsum` and the
+//! SipHash reference implementation. SipHash is keyed with zeros, which
+//! makes it a checksum rather than a PRF.
 
-use sha2::{Digest, Sha256};
+use crate::variants::{VortexHash224, VortexHash256, VortexHash384, VortexHash512};
+use sha1::Sha1;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
+use siphasher::sip::SipHasher24;
+use std::fmt;
+use std::hash::Hasher as _;
+use std::str::FromStr;
+use xxhash_rust::xxh3::Xxh3;
 
-pub struct UniversalHash;
+/// First multicodec code of the private-use range.
+const PRIVATE_USE: u64 = 0x30_0000;
+
+/// A hash function known to the [`REGISTRY`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    Vortex224,
+    Vortex256,
+    Vortex384,
+    Vortex512,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha1,
+    Md5,
+    /// 64-bit XXH3.
+    Xxh3,
+    /// SipHash-2-4 with the all-zero key.
+    SipHash,
+}
+
+/// Registry entry of one [`Algorithm`].
+#[derive(Debug)]
+pub struct AlgorithmInfo {
+    pub algorithm: Algorithm,
+    /// Canonical name, as printed by `Display`.
+    pub name: &'static str,
+    /// Other names accepted by `FromStr`.
+    pub aliases: &'static [&'static str],
+    /// Digest size in bytes.
+    pub output_size: usize,
+    /// ASN.1 object identifier in dotted form.
+    pub oid: Option<&'static str>,
+    /// Multicodec code, as used in a multihash prefix.
+    pub multicodec: u64,
+    /// Creates a hasher for this algorithm.
+    pub new_hasher: fn() -> Box<dyn DynHasher>,
+}
+
+/// Every algorithm, in [`Algorithm`] declaration order.
+pub static REGISTRY: [AlgorithmInfo; 12] = ENTRIES;
+
+// `Algorithm::info` indexes the registry by discriminant.
+const _: () = {
+    let mut i = 0;
+    while i < ENTRIES.len() {
+        assert!(ENTRIES[i].algorithm as usize == i);
+        assert!(Algorithm::ALL[i] as usize == i);
+        i += 1;
+    }
+};
+
+const ENTRIES: [AlgorithmInfo; 12] = [
+    AlgorithmInfo {
+        algorithm: Algorithm::Vortex224,
+        name: "vortex-224",
+        aliases: &["vortex224", "vortexhash-224"],
+        output_size: VortexHash224::OUTPUT_SIZE,
+        oid: None,
+        multicodec: PRIVATE_USE,
+        new_hasher: || Box::new(VortexHash224::new()),
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Vortex256,
+        name: "vortex-256",
+        aliases: &["vortex", "vortex256", "vortexhash", "vortexhash-256"],
+        output_size: VortexHash256::OUTPUT_SIZE,
+        oid: None,
+        multicodec: PRIVATE_USE + 1,
+        new_hasher: || Box::new(VortexHash256::new()),
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Vortex384,
+        name: "vortex-384",
+        aliases: &["vortex384", "vortexhash-384"],
+        output_size: VortexHash384::OUTPUT_SIZE,
+        oid: None,
+        multicodec: PRIVATE_USE + 2,
+        new_hasher: || Box::new(VortexHash384::new()),
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Vortex512,
+        name: "vortex-512",
+        aliases: &["vortex512", "vortexhash-512"],
+        output_size: VortexHash512::OUTPUT_SIZE,
+        oid: None,
+        multicodec: PRIVATE_USE + 3,
+        new_hasher: || Box::new(VortexHash512::new()),
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Sha224,
+        name: "sha224",
+        aliases: &["sha-224", "sha2-224"],
+        output_size: 28,
+        oid: Some("2.16.840.1.101.3.4.2.4"),
+        multicodec: 0x1013,
+        new_hasher: || Box::new(DigestHasher::new(Algorithm::Sha224, Sha224::new())),
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Sha256,
+        name: "sha256",
+        aliases: &["sha-256", "sha2-256"],
+        output_size: 32,
+        oid: Some("2.16.840.1.101.3.4.2.1"),
+        multicodec: 0x12,
+        new_hasher: || Box::new(DigestHasher::new(Algorithm::Sha256, Sha256::new())),
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Sha384,
+        name: "sha384",
+        aliases: &["sha-384", "sha2-384"],
+        output_size: 48,
+        oid: Some("2.16.840.1.101.3.4.2.2"),
+        multicodec: 0x20,
+        new_hasher: || Box::new(DigestHasher::new(Algorithm::Sha384, Sha384::new())),
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Sha512,
+        name: "sha512",
+        aliases: &["sha-512", "sha2-512"],
+        output_size: 64,
+        oid: Some("2.16.840.1.101.3.4.2.3"),
+        multicodec: 0x13,
+        new_hasher: || Box::new(DigestHasher::new(Algorithm::Sha512, Sha512::new())),
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Sha1,
+        name: "sha1",
+        aliases: &["sha-1"],
+        output_size: 20,
+        oid: Some("1.3.14.3.2.26"),
+        multicodec: 0x11,
+        new_hasher: || Box::new(DigestHasher::new(Algorithm::Sha1, Sha1::new())),
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Md5,
+        name: "md5",
+        aliases: &[],
+        output_size: 16,
+        oid: Some("1.2.840.113549.2.5"),
+        multicodec: 0xd5,
+        new_hasher: || Box::new(md5::Context::new()),
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::Xxh3,
+        name: "xxh3",
+        aliases: &["xxh3-64"],
+        output_size: 8,
+        oid: None,
+        multicodec: 0xb3e3,
+        new_hasher: || Box::new(Xxh3::new()),
+    },
+    AlgorithmInfo {
+        algorithm: Algorithm::SipHash,
+        name: "siphash",
+        aliases: &["siphash-2-4", "siphash24"],
+        output_size: 8,
+        oid: None,
+        multicodec: PRIVATE_USE + 4,
+        new_hasher: || Box::new(SipHasher24::new_with_keys(0, 0)),
+    },
+];
+
+impl Algorithm {
+    /// Every algorithm, in [`REGISTRY`] order.
+    pub const ALL: [Algorithm; 12] = [
+        Algorithm::Vortex224,
+        Algorithm::Vortex256,
+        Algorithm::Vortex384,
+        Algorithm::Vortex512,
+        Algorithm::Sha224,
+        Algorithm::Sha256,
+        Algorithm::Sha384,
+        Algorithm::Sha512,
+        Algorithm::Sha1,
+        Algorithm::Md5,
+        Algorithm::Xxh3,
+        Algorithm::SipHash,
+    ];
+
+    pub fn info(self) -> &'static AlgorithmInfo {
+        &REGISTRY[self as usize]
+    }
+
+    pub fn name(self) -> &'static str {
+        self.info().name
+    }
+
+    pub fn output_size(self) -> usize {
+        self.info().output_size
+    }
+
+    pub fn oid(self) -> Option<&'static str> {
+        self.info().oid
+    }
+
+    pub fn multicodec(self) -> u64 {
+        self.info().multicodec
+    }
+
+    /// The algorithm with multicodec code `code`.
+    pub fn from_multicodec(code: u64) -> Option<Self> {
+        REGISTRY
+            .iter()
+            .find(|info| info.multicodec == code)
+            .map(|info| info.algorithm)
+    }
+
+    pub fn hasher(self) -> Box<dyn DynHasher> {
+        (self.info().new_hasher)()
+    }
+
+    /// One-shot digest of `data`.
+    pub fn digest(self, data: &[u8]) -> Vec<u8> {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = &'static str;
+
+    /// Parses a canonical name or alias, ignoring ASCII case.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        REGISTRY
+            .iter()
+            .find(|info| {
+                info.name.eq_ignore_ascii_case(name)
+                    || info
+                        .aliases
+                        .iter()
+                        .any(|alias| alias.eq_ignore_ascii_case(name))
+            })
+            .map(|info| info.algorithm)
+            .ok_or("unknown hash algorithm")
+    }
+}
+
+/// Object-safe streaming hasher behind [`UniversalHash`].
+pub trait DynHasher: Send {
+    fn algorithm(&self) -> Algorithm;
+
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the digest, [`Algorithm::output_size`] bytes long.
+    fn finalize(self: Box<Self>) -> Vec<u8>;
+
+    /// Returns the digest and restarts with no input.
+    fn finalize_reset(&mut self) -> Vec<u8>;
+
+    fn reset(&mut self);
+
+    fn box_clone(&self) -> Box<dyn DynHasher>;
+}
+
+macro_rules! impl_dyn_hasher_for_variant {
+    ($name:ident, $algorithm:expr) => {
+        impl DynHasher for $name {
+            fn algorithm(&self) -> Algorithm {
+                $algorithm
+            }
+
+            fn update(&mut self, data: &[u8]) {
+                $name::update(self, data);
+            }
+
+            fn finalize(self: Box<Self>) -> Vec<u8> {
+                $name::finalize(*self).to_vec()
+            }
+
+            fn finalize_reset(&mut self) -> Vec<u8> {
+                std::mem::take(self).finalize().to_vec()
+            }
+
+            fn reset(&mut self) {
+                *self = $name::new();
+            }
+
+            fn box_clone(&self) -> Box<dyn DynHasher> {
+                Box::new(self.clone())
+            }
+        }
+    };
+}
+
+impl_dyn_hasher_for_variant!(VortexHash224, Algorithm::Vortex224);
+impl_dyn_hasher_for_variant!(VortexHash256, Algorithm::Vortex256);
+impl_dyn_hasher_for_variant!(VortexHash384, Algorithm::Vortex384);
+impl_dyn_hasher_for_variant!(VortexHash512, Algorithm::Vortex512);
+
+/// [`DynHasher`] over a RustCrypto [`Digest`].
+#[derive(Clone)]
+struct DigestHasher<D> {
+    algorithm: Algorithm,
+    inner: D,
+}
+
+impl<D> DigestHasher<D> {
+    fn new(algorithm: Algorithm, inner: D) -> Self {
+        Self { algorithm, inner }
+    }
+}
+
+impl<D: Digest + Clone + Send + 'static> DynHasher for DigestHasher<D> {
+    fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(&mut self.inner, data);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        self.inner.finalize().to_vec()
+    }
+
+    fn finalize_reset(&mut self) -> Vec<u8> {
+        std::mem::replace(&mut self.inner, D::new())
+            .finalize()
+            .to_vec()
+    }
+
+    fn reset(&mut self) {
+        self.inner = D::new();
+    }
+
+    fn box_clone(&self) -> Box<dyn DynHasher> {
+        Box::new(self.clone())
+    }
+}
+
+impl DynHasher for md5::Context {
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Md5
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.consume(data);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        self.compute().0.to_vec()
+    }
+
+    fn finalize_reset(&mut self) -> Vec<u8> {
+        std::mem::replace(self, md5::Context::new())
+            .compute()
+            .0
+            .to_vec()
+    }
+
+    fn reset(&mut self) {
+        *self = md5::Context::new();
+    }
+
+    fn box_clone(&self) -> Box<dyn DynHasher> {
+        Box::new(self.clone())
+    }
+}
+
+impl DynHasher for Xxh3 {
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::Xxh3
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        Xxh3::update(self, data);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        self.digest().to_be_bytes().to_vec()
+    }
+
+    fn finalize_reset(&mut self) -> Vec<u8> {
+        let digest = self.digest();
+        Xxh3::reset(self);
+        digest.to_be_bytes().to_vec()
+    }
+
+    fn reset(&mut self) {
+        Xxh3::reset(self);
+    }
+
+    fn box_clone(&self) -> Box<dyn DynHasher> {
+        Box::new(self.clone())
+    }
+}
+
+impl DynHasher for SipHasher24 {
+    fn algorithm(&self) -> Algorithm {
+        Algorithm::SipHash
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.write(data);
+    }
+
+    fn finalize(self: Box<Self>) -> Vec<u8> {
+        self.finish().to_le_bytes().to_vec()
+    }
+
+    fn finalize_reset(&mut self) -> Vec<u8> {
+        let digest = self.finish();
+        DynHasher::reset(self);
+        digest.to_le_bytes().to_vec()
+    }
+
+    fn reset(&mut self) {
+        *self = SipHasher24::new_with_keys(0, 0);
+    }
+
+    fn box_clone(&self) -> Box<dyn DynHasher> {
+        Box::new(*self)
+    }
+}
+
+/// Streaming hasher for an algorithm chosen at run time.
+///
+/// ```
+/// use vortex_hash::compatibility::UniversalHash;
+///
+/// let mut hasher = UniversalHash::from_name("sha256").unwrap();
+/// hasher.update(b"abc");
+/// assert_eq!(hasher.finalize().len(), 32);
+/// ```
+pub struct UniversalHash {
+    hasher: Box<dyn DynHasher>,
+}
 
 impl UniversalHash {
+    pub fn new(algorithm: Algorithm) -> Self {
+        Self {
+            hasher: algorithm.hasher(),
+        }
+    }
+
+    /// Creates a hasher from an algorithm name; see [`Algorithm::from_str`].
+    pub fn from_name(name: &str) -> Result<Self, &'static str> {
+        Ok(Self::new(name.parse()?))
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.hasher.algorithm()
+    }
+
+    pub fn output_size(&self) -> usize {
+        self.algorithm().output_size()
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        self.hasher.finalize()
+    }
+
+    pub fn finalize_reset(&mut self) -> Vec<u8> {
+        self.hasher.finalize_reset()
+    }
+
+    pub fn reset(&mut self) {
+        self.hasher.reset();
+    }
+
+    /// SHA-256 of `data`, the digest legacy systems store.
     pub fn hash_compatible(data: &[u8]) -> [u8; 32] {
         let mut hasher = Sha256::new();
         Digest::update(&mut hasher, data);
@@ -15,6 +506,22 @@ impl UniversalHash {
     }
 }
 
+impl Clone for UniversalHash {
+    fn clone(&self) -> Self {
+        Self {
+            hasher: self.hasher.box_clone(),
+        }
+    }
+}
+
+impl fmt::Debug for UniversalHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("UniversalHash")
+            .field(&self.algorithm())
+            .finish()
+    }
+}
+
 pub fn legacy_compatibility_check() -> bool {
     true
 }
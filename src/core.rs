@@ -1,73 +1,325 @@
+use crate::permutation::Permutation;
 use crate::security::SecurityConfig;
+use std::io;
+use zeroize::Zeroize;
+
+/// Version of the sponge construction (padding, domain separation and
+/// permutation). Digests produced by different versions are unrelated, so
+/// stored digests should be tagged with the version that produced them.
+///
+/// Version 1 was the original 64-byte construction without padding and with
+/// a fixed round count. Version 2 uses the 1024-bit state and the
+/// [`Permutation`] from the whitepaper, takes rounds and capacity from
+/// [`SecurityConfig`], and adds the `pad10*1` rule with the
+/// domain-separation bytes in [`domain`].
+pub const ALGORITHM_VERSION: u32 = 2;
+
+/// Size of the sponge state in bytes (1024 bits).
+pub const STATE_SIZE: usize = 128;
+
+/// Capacity in bytes of the 256-bit security level, used by the modes built
+/// directly on [`VortexHash::with_capacity`].
+pub(crate) const DEFAULT_CAPACITY: usize = 64;
+
+/// Domain-separation bytes mixed into the final block by the padding rule.
+///
+/// Every mode of operation uses its own byte, so two modes can never produce
+/// the same output even when they absorb identical input. Values must be in
+/// `0x01..=0x7F` so the padding stays injective.
+pub mod domain {
+    /// Plain hashing (`hash`, `hash_secure`, [`super::VortexHash::squeeze`]).
+    pub const HASH: u8 = 0x01;
+    /// Keyed hashing ([`crate::mac::VortexMac`]).
+    pub const MAC: u8 = 0x02;
+    /// Extendable-output mode ([`super::VortexHash::finalize_xof`]).
+    pub const XOF: u8 = 0x03;
+    /// [`crate::variants::VortexHash224`].
+    pub const HASH_224: u8 = 0x04;
+    /// [`crate::variants::VortexHash384`].
+    pub const HASH_384: u8 = 0x05;
+    /// [`crate::variants::VortexHash512`].
+    pub const HASH_512: u8 = 0x06;
+    /// The key-prefix `VortexHash::hmac` kept under the `legacy_api` feature.
+    pub const LEGACY_MAC: u8 = 0x07;
+    /// `std::hash::Hasher` implementation ([`crate::hasher::VortexHasher`]).
+    pub const STD_HASHER: u8 = 0x08;
+    /// Memory-hard password hashing ([`crate::password`]).
+    pub const PASSWORD: u8 = 0x09;
+    /// Tweakable hash of the hash-based signatures ([`crate::wots`]).
+    pub const SIGNATURE: u8 = 0x0A;
+    /// Secret-key derivation of the hash-based signatures.
+    pub const SIGNATURE_PRF: u8 = 0x0B;
+    /// Message digest of the hash-based signatures.
+    pub const SIGNATURE_MESSAGE: u8 = 0x0C;
+    /// Message randomizer of the hash-based signatures.
+    pub const SIGNATURE_RANDOMIZER: u8 = 0x0D;
+    /// Chunk chaining values of the tree mode ([`crate::tree`]).
+    pub const TREE_LEAF: u8 = 0x0E;
+    /// Parent chaining values of the tree mode.
+    pub const TREE_PARENT: u8 = 0x0F;
+    /// Root digest of the tree mode.
+    pub const TREE_ROOT: u8 = 0x10;
+    /// Leaf hashes of [`crate::merkle`].
+    pub const MERKLE_LEAF: u8 = 0x11;
+    /// Interior node hashes of [`crate::merkle`].
+    pub const MERKLE_NODE: u8 = 0x12;
+    /// Root of the empty [`crate::merkle::MerkleTree`].
+    pub const MERKLE_EMPTY: u8 = 0x13;
+    /// Initial state of the authenticated encryption mode
+    /// ([`crate::aead`]); placed in the capacity rather than padded.
+    pub const AEAD: u8 = 0x14;
+    /// Seeding and reseeding of [`crate::rng::VortexRng`].
+    pub const RNG_SEED: u8 = 0x15;
+    /// Output blocks of [`crate::rng::VortexRng`].
+    pub const RNG_OUTPUT: u8 = 0x16;
+}
 
 #[derive(Debug, Clone)]
 pub struct VortexHash {
-    state: [u8; 64], // Sponge state
+    state: [u8; STATE_SIZE], // Sponge state
     rate: usize,
+    permutation: Permutation,
+    pos: usize, // Bytes absorbed into the current block
 }
 
 impl VortexHash {
-    pub fn new(_config: &SecurityConfig) -> Self {
+    /// Creates a hasher whose round count and rate/capacity split are taken
+    /// from `config`.
+    ///
+    /// Returns an error if `config` does not pass [`SecurityConfig::validate`].
+    pub fn new(config: &SecurityConfig) -> Result<Self, &'static str> {
+        if !config.validate() {
+            return Err("Invalid security configuration");
+        }
+        let capacity = config.capacity().ok_or("Unsupported security level")?;
+        Ok(Self {
+            state: [0u8; STATE_SIZE],
+            rate: STATE_SIZE - capacity, // Bytes processed per block
+            permutation: Permutation::new(config.rounds),
+            pos: 0,
+        })
+    }
+
+    /// Creates a sponge with an explicit capacity, for modes that fix their
+    /// own parameters instead of taking them from a [`SecurityConfig`].
+    pub(crate) fn with_capacity(capacity: usize, rounds: u32) -> Self {
+        debug_assert!(capacity < STATE_SIZE);
         Self {
-            state: [0u8; 64],
-            rate: 32, // Bytes processed per block
+            state: [0u8; STATE_SIZE],
+            rate: STATE_SIZE - capacity,
+            permutation: Permutation::new(rounds),
+            pos: 0,
         }
     }
 
-    pub fn absorb(&mut self, data: &[u8]) {
-        let mut offset = 0;
-        while offset < data.len() {
-            let chunk_size = std::cmp::min(self.rate, data.len() - offset);
-            let chunk = &data[offset..offset + chunk_size];
-            for (i, &byte) in chunk.iter().enumerate() {
-                self.state[i] ^= byte;
+    /// Bytes absorbed per permutation call.
+    pub(crate) fn rate(&self) -> usize {
+        self.rate
+    }
+
+    /// Absorbs `data`.
+    ///
+    /// Input is XORed into the rate part of the state, which doubles as the
+    /// block buffer: a partial block is carried over to the next call, so
+    /// splitting the input across calls never changes the result.
+    pub fn absorb(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let take = (self.rate - self.pos).min(data.len());
+            let block = &mut self.state[self.pos..self.pos + take];
+            for (byte, &input) in block.iter_mut().zip(&data[..take]) {
+                *byte ^= input;
             }
-            offset += chunk_size;
-            if offset % self.rate == 0 {
+            self.pos += take;
+            data = &data[take..];
+            if self.pos == self.rate {
                 self.permute();
+                self.pos = 0;
             }
         }
     }
 
+    /// Streaming alias of [`VortexHash::absorb`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vortex_hash::{hash, VortexHash};
+    ///
+    /// let mut hasher = VortexHash::default();
+    /// hasher.update(b"Hello, ");
+    /// hasher.update(b"Vortex!");
+    /// assert_eq!(hasher.finalize(), hash(b"Hello, Vortex!"));
+    /// ```
+    pub fn update(&mut self, data: &[u8]) {
+        self.absorb(data);
+    }
+
+    /// Returns the digest of everything absorbed so far.
+    pub fn finalize(mut self) -> [u8; 32] {
+        self.squeeze_with_domain(domain::HASH)
+    }
+
+    /// Returns the digest and resets the hasher to its initial state, keeping
+    /// its configuration.
+    pub fn finalize_reset(&mut self) -> [u8; 32] {
+        let output = self.squeeze_with_domain(domain::HASH);
+        self.reset();
+        output
+    }
+
+    /// Discards all absorbed input, keeping the configuration.
+    pub fn reset(&mut self) {
+        self.zeroize();
+    }
+
+    /// Same as [`VortexHash::finalize_reset`].
     pub fn squeeze(&mut self) -> [u8; 32] {
-        self.permute();
+        self.finalize_reset()
+    }
+
+    /// Finishes absorbing and returns a reader producing an arbitrary amount
+    /// of output, permuting the state between rate-sized blocks.
+    ///
+    /// The XOF uses its own domain byte, so its output is unrelated to
+    /// [`VortexHash::squeeze`] for the same input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Read;
+    /// use vortex_hash::VortexHash;
+    ///
+    /// let mut hasher = VortexHash::default();
+    /// hasher.absorb(b"key material");
+    /// let mut reader = hasher.finalize_xof();
+    /// let mut okm = [0u8; 128];
+    /// reader.read_exact(&mut okm).unwrap();
+    /// ```
+    pub fn finalize_xof(mut self) -> XofReader {
+        self.pad(domain::XOF);
+        XofReader { sponge: self }
+    }
+
+    fn squeeze_with_domain(&mut self, domain: u8) -> [u8; 32] {
         let mut output = [0u8; 32];
-        output.copy_from_slice(&self.state[0..32]);
+        self.finalize_into(domain, &mut output);
         output
     }
 
-    fn permute(&mut self) {
-        // Simple ARX-based permutation for demonstration
-        for _ in 0..8 {
-            // 8 rounds
-            for i in 0..64 {
-                self.state[i] = self.state[i].wrapping_add(self.state[(i + 1) % 64]);
-                self.state[i] = self.state[i].rotate_left(13);
-                self.state[i] ^= self.state[(i + 17) % 64];
+    /// Pads with `domain` and fills `output` from the squeezing phase.
+    pub(crate) fn finalize_into(&mut self, domain: u8, output: &mut [u8]) {
+        self.pad(domain);
+        self.squeeze_into(output);
+    }
+
+    /// Applies the `pad10*1` rule with the given domain byte and permutes,
+    /// switching the sponge to the squeezing phase.
+    ///
+    /// The domain byte is XORed at the first free position of the current
+    /// block and `0x80` into its last byte, so inputs that differ only in
+    /// trailing zero bytes, or in the mode that hashed them, never collide.
+    fn pad(&mut self, domain: u8) {
+        debug_assert!(domain != 0 && domain < 0x80);
+        self.state[self.pos] ^= domain;
+        self.state[self.rate - 1] ^= 0x80;
+        self.permute();
+        self.pos = 0;
+    }
+
+    /// Copies output from the rate part of the state, permuting whenever a
+    /// full block has been read.
+    fn squeeze_into(&mut self, output: &mut [u8]) {
+        for byte in output {
+            if self.pos == self.rate {
+                self.permute();
+                self.pos = 0;
             }
+            *byte = self.state[self.pos];
+            self.pos += 1;
         }
     }
 
-    pub fn hash_secure(data: &[u8], _config: &SecurityConfig) -> [u8; 32] {
-        let mut hasher = VortexHash::new(&SecurityConfig::default());
+    fn permute(&mut self) {
+        self.permutation.permute_bytes(&mut self.state);
+    }
+
+    /// Hashes `data` with the rounds and security level from `config`.
+    ///
+    /// Returns an error if `config` does not pass [`SecurityConfig::validate`].
+    pub fn hash_secure(data: &[u8], config: &SecurityConfig) -> Result<[u8; 32], &'static str> {
+        let mut hasher = VortexHash::new(config)?;
         hasher.absorb(data);
-        hasher.squeeze()
+        Ok(hasher.finalize())
     }
 
+    /// Legacy keyed hash that absorbs `key` followed by `data`.
+    ///
+    /// The key length is not framed, so `hmac(b"ab", b"c")` equals
+    /// `hmac(b"a", b"bc")`. Use [`crate::mac::VortexMac`] instead.
+    #[cfg(feature = "legacy_api")]
     pub fn hmac(key: &[u8], data: &[u8]) -> [u8; 32] {
-        let config = SecurityConfig::default();
-        let mut hasher = VortexHash::new(&config);
+        let mut hasher = VortexHash::default();
         hasher.absorb(key);
         hasher.absorb(data);
-        hasher.squeeze()
+        hasher.squeeze_with_domain(domain::LEGACY_MAC)
     }
 
     pub fn hash(data: &[u8]) -> [u8; 32] {
-        let config = SecurityConfig::default();
-        VortexHash::hash_secure(data, &config)
+        let mut hasher = VortexHash::default();
+        hasher.absorb(data);
+        hasher.finalize()
+    }
+}
+
+impl Zeroize for VortexHash {
+    fn zeroize(&mut self) {
+        self.state.zeroize();
+        self.pos = 0;
+    }
+}
+
+impl Default for VortexHash {
+    /// Hasher for the default [`SecurityConfig`] (64 rounds, 256-bit level).
+    fn default() -> Self {
+        Self::new(&SecurityConfig::default()).expect("default SecurityConfig is valid")
     }
 }
 
-pub fn hash_secure(data: &[u8], config: &SecurityConfig) -> [u8; 32] {
+/// Output reader of the extendable-output mode, returned by
+/// [`VortexHash::finalize_xof`].
+#[derive(Debug, Clone)]
+pub struct XofReader {
+    sponge: VortexHash,
+}
+
+impl XofReader {
+    /// Fills `output` with the next bytes of the output stream.
+    pub fn squeeze(&mut self, output: &mut [u8]) {
+        self.sponge.squeeze_into(output);
+    }
+}
+
+impl io::Read for XofReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.squeeze(buf);
+        Ok(buf.len())
+    }
+}
+
+pub fn hash_secure(data: &[u8], config: &SecurityConfig) -> Result<[u8; 32], &'static str> {
     VortexHash::hash_secure(data, config)
 }
+
+/// Fills `output` with the extendable output of `data` under `config`.
+///
+/// Returns an error if `config` does not pass [`SecurityConfig::validate`].
+pub fn hash_to_secure(
+    data: &[u8],
+    config: &SecurityConfig,
+    output: &mut [u8],
+) -> Result<(), &'static str> {
+    let mut hasher = VortexHash::new(config)?;
+    hasher.absorb(data);
+    hasher.finalize_xof().squeeze(output);
+    Ok(())
+}
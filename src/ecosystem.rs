@@ -1,4 +1,38 @@
 //! Ecosystem integration for VortexHash
+//!
+//! Implements the RustCrypto [`digest`] traits for [`VortexHash`] (as a hash
+//! function and XOF) and for [`VortexMac`] (as a MAC), so code written
+//! against `digest::Digest`, `digest::ExtendableOutput` or `digest::Mac`
+//! accepts VortexHash directly. [`VortexAead`] implements the [`aead`]
+//! crate's `AeadInPlace` (and through it `Aead`).
+//!
+//! HMAC and HKDF need a block-level core. `digest` seals `CoreProxy` to its
+//! own `CoreWrapper`, so `VortexHash` itself can only be used through
+//! `hmac::SimpleHmac<VortexHash>` and `hkdf::SimpleHkdf<VortexHash>`;
+//! [`VortexHashDigest`] wraps [`VortexHashCore`] in a `CoreWrapper` and
+//! works with `hmac::Hmac` and `hkdf::Hkdf`, with the same output.
+//!
+//! The trait implementations use the default [`crate::SecurityConfig`]
+//! through `Default`; a hasher built with `VortexHash::new` keeps its own
+//! configuration when used through the traits.
+
+use crate::aead::{VortexAead, NONCE_SIZE, TAG_SIZE};
+use crate::core::{VortexHash, XofReader};
+use crate::mac::VortexMac;
+use aead::consts::{U0, U16};
+use aead::{AeadCore, AeadInPlace, Nonce, Tag};
+use digest::block_buffer::Eager;
+use digest::consts::{U32, U64};
+use digest::core_api::{
+    AlgorithmName, Block, Buffer, BufferKindUser, CoreWrapper, FixedOutputCore, UpdateCore,
+};
+use digest::{
+    crypto_common::{BlockSizeUser, InvalidLength, Key, KeyInit, KeySizeUser},
+    ExtendableOutput, ExtendableOutputReset, FixedOutput, FixedOutputReset, HashMarker, MacMarker,
+    Output, OutputSizeUser, Reset, Update,
+};
+use std::fmt;
+
 pub struct EcosystemConfig {
     pub integration_level: u32,
 }
@@ -18,3 +52,204 @@ pub fn ecosystem_compatibility_check() -> bool {
 pub fn get_ecosystem_version() -> &'static str {
     "1.0.0"
 }
+
+impl HashMarker for VortexHash {}
+
+impl OutputSizeUser for VortexHash {
+    type OutputSize = U32;
+}
+
+/// Block size used by HMAC for key padding: the sponge rate of the default
+/// configuration.
+impl BlockSizeUser for VortexHash {
+    type BlockSize = U64;
+}
+
+impl Update for VortexHash {
+    fn update(&mut self, data: &[u8]) {
+        self.absorb(data);
+    }
+}
+
+impl FixedOutput for VortexHash {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.finalize());
+    }
+}
+
+impl FixedOutputReset for VortexHash {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.finalize_reset());
+    }
+}
+
+impl Reset for VortexHash {
+    fn reset(&mut self) {
+        VortexHash::reset(self);
+    }
+}
+
+impl ExtendableOutput for VortexHash {
+    type Reader = XofReader;
+
+    fn finalize_xof(self) -> Self::Reader {
+        VortexHash::finalize_xof(self)
+    }
+}
+
+impl ExtendableOutputReset for VortexHash {
+    fn finalize_xof_reset(&mut self) -> Self::Reader {
+        let reader = self.clone().finalize_xof();
+        VortexHash::reset(self);
+        reader
+    }
+}
+
+/// Block-level core of [`VortexHash`] with the default configuration.
+///
+/// Whole 64-byte blocks are absorbed as they arrive and the buffered tail at
+/// finalization, which gives the same digest as absorbing the input in one
+/// call.
+#[derive(Debug, Clone, Default)]
+pub struct VortexHashCore {
+    sponge: VortexHash,
+}
+
+/// [`VortexHash`] with the default configuration as a `CoreWrapper`, for
+/// `hmac::Hmac` and `hkdf::Hkdf`.
+pub type VortexHashDigest = CoreWrapper<VortexHashCore>;
+
+impl HashMarker for VortexHashCore {}
+
+impl BlockSizeUser for VortexHashCore {
+    type BlockSize = U64;
+}
+
+impl BufferKindUser for VortexHashCore {
+    type BufferKind = Eager;
+}
+
+impl OutputSizeUser for VortexHashCore {
+    type OutputSize = U32;
+}
+
+impl UpdateCore for VortexHashCore {
+    fn update_blocks(&mut self, blocks: &[Block<Self>]) {
+        for block in blocks {
+            self.sponge.absorb(block);
+        }
+    }
+}
+
+impl FixedOutputCore for VortexHashCore {
+    fn finalize_fixed_core(&mut self, buffer: &mut Buffer<Self>, out: &mut Output<Self>) {
+        self.sponge.absorb(buffer.get_data());
+        out.copy_from_slice(&self.sponge.finalize_reset());
+    }
+}
+
+impl Reset for VortexHashCore {
+    fn reset(&mut self) {
+        self.sponge.reset();
+    }
+}
+
+impl AlgorithmName for VortexHashCore {
+    fn write_alg_name(f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("VortexHash")
+    }
+}
+
+impl digest::XofReader for XofReader {
+    fn read(&mut self, buffer: &mut [u8]) {
+        self.squeeze(buffer);
+    }
+}
+
+impl MacMarker for VortexMac {}
+
+/// Nominal key size for `KeyInit::new`; `new_from_slice` accepts keys of
+/// any length.
+impl KeySizeUser for VortexMac {
+    type KeySize = U32;
+}
+
+impl KeyInit for VortexMac {
+    fn new(key: &Key<Self>) -> Self {
+        VortexMac::new(key)
+    }
+
+    fn new_from_slice(key: &[u8]) -> Result<Self, InvalidLength> {
+        Ok(VortexMac::new(key))
+    }
+}
+
+impl OutputSizeUser for VortexMac {
+    type OutputSize = U32;
+}
+
+impl Update for VortexMac {
+    fn update(&mut self, data: &[u8]) {
+        VortexMac::update(self, data);
+    }
+}
+
+impl FixedOutput for VortexMac {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&self.finalize());
+    }
+}
+
+impl FixedOutputReset for VortexMac {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        self.finalize_tag_mut(out);
+        VortexMac::reset(self);
+    }
+}
+
+impl Reset for VortexMac {
+    fn reset(&mut self) {
+        VortexMac::reset(self);
+    }
+}
+
+impl KeySizeUser for VortexAead {
+    type KeySize = U32;
+}
+
+impl KeyInit for VortexAead {
+    fn new(key: &Key<Self>) -> Self {
+        VortexAead::new(key.as_ref())
+    }
+}
+
+impl AeadCore for VortexAead {
+    type NonceSize = U16;
+    type TagSize = U32;
+    type CiphertextOverhead = U0;
+}
+
+impl AeadInPlace for VortexAead {
+    fn encrypt_in_place_detached(
+        &self,
+        nonce: &Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+    ) -> aead::Result<Tag<Self>> {
+        let nonce: &[u8; NONCE_SIZE] = nonce.as_ref();
+        let tag = VortexAead::encrypt_in_place_detached(self, nonce, associated_data, buffer);
+        Ok(tag.into())
+    }
+
+    fn decrypt_in_place_detached(
+        &self,
+        nonce: &Nonce<Self>,
+        associated_data: &[u8],
+        buffer: &mut [u8],
+        tag: &Tag<Self>,
+    ) -> aead::Result<()> {
+        let tag: &[u8; TAG_SIZE] = tag.as_ref();
+        VortexAead::decrypt_in_place_detached(self, nonce.as_ref(), associated_data, buffer, tag)
+            .map_err(|_| aead::Error)
+    }
+}
@@ -4,6 +4,10 @@ use crate::core::VortexHash;
 pub struct UltraPerformance;
 
 impl UltraPerformance {
+    /// Serial [`VortexHash::hash`] under another name.
+    #[deprecated(
+        note = "same digest as `hash` with no acceleration; use `TreeHash::digest` to hash large inputs in parallel"
+    )]
     pub fn hash_ultra_optimized(data: &[u8]) -> [u8; 32] {
         // Stub for GPU/hardware accelerated hashing
         // In real implementation, this would use CUDA/Vulkan APIs
@@ -0,0 +1,150 @@
+//! `std::hash` integration for VortexHash
+//!
+//! [`VortexBuildHasher`] builds keyed [`VortexHasher`]s for `HashMap` and
+//! `HashSet`. Each builder draws a random 128-bit key, so an attacker who
+//! does not know the key cannot precompute colliding map keys (hash
+//! flooding). [`VortexFastBuildHasher`] is an unkeyed variant for keys that
+//! are not attacker-controlled.
+//!
+//! Both use a reduced-round permutation and a 256-bit capacity: the output
+//! is only 64 bits and is never exposed, so the full 64-round digest would
+//! be wasted work. Digests are not compatible with [`crate::hash`].
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use vortex_hash::VortexBuildHasher;
+//!
+//! let mut map = HashMap::with_hasher(VortexBuildHasher::new());
+//! map.insert("key", 1);
+//! assert_eq!(map.get("key"), Some(&1));
+//! ```
+
+use crate::core::domain;
+use crate::permutation::{Permutation, LANES};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use std::hash::{BuildHasher, Hasher};
+
+/// Permutation rounds of the keyed hasher (one pass over all eight S-boxes).
+pub const KEYED_ROUNDS: u32 = 8;
+/// Permutation rounds of the unkeyed fast hasher.
+pub const FAST_ROUNDS: u32 = 4;
+
+/// Rate in 64-bit lanes (256-bit capacity).
+const RATE_LANES: usize = LANES - 4;
+const RATE: usize = RATE_LANES * 8;
+
+/// Builder of keyed [`VortexHasher`]s with a random per-instance key.
+#[derive(Debug, Clone)]
+pub struct VortexBuildHasher {
+    initial: [u64; LANES],
+}
+
+impl VortexBuildHasher {
+    /// Creates a builder with a fresh random key from the operating
+    /// system's entropy source.
+    pub fn new() -> Self {
+        Self::with_keys(OsRng.next_u64(), OsRng.next_u64())
+    }
+
+    /// Creates a builder with an explicit key, for reproducible hashing.
+    pub fn with_keys(k0: u64, k1: u64) -> Self {
+        Self {
+            initial: keyed_state(k0, k1, KEYED_ROUNDS),
+        }
+    }
+}
+
+impl Default for VortexBuildHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BuildHasher for VortexBuildHasher {
+    type Hasher = VortexHasher;
+
+    fn build_hasher(&self) -> VortexHasher {
+        VortexHasher::from_state(self.initial, KEYED_ROUNDS)
+    }
+}
+
+/// Builder of unkeyed [`VortexHasher`]s.
+///
+/// Faster than [`VortexBuildHasher`], but offers no protection against
+/// hash flooding: only use it when keys are not attacker-controlled.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VortexFastBuildHasher;
+
+impl BuildHasher for VortexFastBuildHasher {
+    type Hasher = VortexHasher;
+
+    fn build_hasher(&self) -> VortexHasher {
+        VortexHasher::from_state([0u64; LANES], FAST_ROUNDS)
+    }
+}
+
+/// Reduced-round VortexHash sponge implementing [`Hasher`].
+#[derive(Debug, Clone)]
+pub struct VortexHasher {
+    state: [u64; LANES],
+    buffer: [u8; RATE],
+    len: usize,
+    permutation: Permutation,
+}
+
+impl VortexHasher {
+    fn from_state(state: [u64; LANES], rounds: u32) -> Self {
+        Self {
+            state,
+            buffer: [0u8; RATE],
+            len: 0,
+            permutation: Permutation::new(rounds),
+        }
+    }
+
+    fn absorb_buffer(state: &mut [u64; LANES], buffer: &[u8; RATE]) {
+        for (lane, bytes) in state.iter_mut().zip(buffer.chunks_exact(8)) {
+            *lane ^= u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+    }
+}
+
+impl Hasher for VortexHasher {
+    fn write(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() {
+            let take = (RATE - self.len).min(bytes.len());
+            self.buffer[self.len..self.len + take].copy_from_slice(&bytes[..take]);
+            self.len += take;
+            bytes = &bytes[take..];
+            if self.len == RATE {
+                Self::absorb_buffer(&mut self.state, &self.buffer);
+                self.permutation.permute(&mut self.state);
+                self.len = 0;
+            }
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        let mut state = self.state;
+        let mut buffer = [0u8; RATE];
+        buffer[..self.len].copy_from_slice(&self.buffer[..self.len]);
+        buffer[self.len] ^= domain::STD_HASHER;
+        buffer[RATE - 1] ^= 0x80;
+        Self::absorb_buffer(&mut state, &buffer);
+        self.permutation.permute(&mut state);
+        state[0]
+    }
+}
+
+/// Initial state with the key in the capacity lanes, permuted once so the
+/// key is spread over the whole state before any input is absorbed.
+fn keyed_state(k0: u64, k1: u64, rounds: u32) -> [u64; LANES] {
+    let mut state = [0u64; LANES];
+    state[RATE_LANES] = k0;
+    state[RATE_LANES + 1] = k1;
+    Permutation::new(rounds).permute(&mut state);
+    state
+}
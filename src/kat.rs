@@ -0,0 +1,332 @@
+//! Known-answer test vectors for VortexHash
+//!
+//! The vectors pin the exact output of each mode for the construction
+//! identified by [`VERSION`]. Digests stored by an older version will not
+//! match these vectors; tag stored digests with the version that produced
+//! them (see [`crate::core::ALGORITHM_VERSION`]).
+
+/// Construction version the vectors below were generated with.
+pub const VERSION: u32 = crate::core::ALGORITHM_VERSION;
+
+/// Expected `hash(input)` output, hex encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct HashVector {
+    pub input: &'static [u8],
+    pub digest: &'static str,
+}
+
+/// Expected `hash_secure(input, config)` output for a configuration with
+/// the given `rounds` and `security_level`, hex encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct SecureHashVector {
+    pub rounds: u32,
+    pub security_level: u32,
+    pub input: &'static [u8],
+    pub digest: &'static str,
+}
+
+/// Expected digest of the fixed-length variant with `bits` output bits.
+#[derive(Debug, Clone, Copy)]
+pub struct VariantVector {
+    pub bits: usize,
+    pub input: &'static [u8],
+    pub digest: &'static str,
+}
+
+/// Expected 32-byte `VortexMac` tag for `key`, `customization` and
+/// `input`, hex encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct MacVector {
+    pub key: &'static [u8],
+    pub customization: &'static [u8],
+    pub input: &'static [u8],
+    pub tag: &'static str,
+}
+
+/// Expected first bytes of the XOF output for `input`, hex encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct XofVector {
+    pub input: &'static [u8],
+    pub output: &'static str,
+}
+
+/// Expected `kdf::derive` output for `salt`, `ikm` and `info`, hex encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct KdfVector {
+    pub salt: &'static [u8],
+    pub ikm: &'static [u8],
+    pub info: &'static [u8],
+    pub okm: &'static str,
+}
+
+/// Expected `VortexAead::encrypt` output (`ciphertext || tag`) for `key`,
+/// `nonce`, `associated_data` and `plaintext`, hex encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct AeadVector {
+    pub key: &'static [u8; 32],
+    pub nonce: &'static [u8; 16],
+    pub associated_data: &'static [u8],
+    pub plaintext: &'static [u8],
+    pub ciphertext: &'static str,
+}
+
+/// Expected first 64 bytes from `VortexRng::from_seed(seed)`, hex
+/// encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct RngVector {
+    pub seed: &'static [u8; 32],
+    pub output: &'static str,
+}
+
+/// Expected `TreeHash::digest` of the first `length` bytes of the pattern
+/// `0, 1, ..., 250, 0, 1, ...`, hex encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct TreeVector {
+    pub length: usize,
+    pub digest: &'static str,
+}
+
+/// Expected raw `password::hash_raw` output for the given costs, hex
+/// encoded.
+#[derive(Debug, Clone, Copy)]
+pub struct PasswordVector {
+    pub password: &'static [u8],
+    pub salt: &'static [u8],
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+    pub digest: &'static str,
+}
+
+/// `Permutation::new(64)` applied to the all-zero state, hex encoded.
+pub const PERMUTATION_ZERO_STATE: &str = concat!(
+    "60f365c61d47539067e460366093c70c808ee3a007691cd602b19dc4b1e87177",
+    "8ab5d81d91630121cdfef8fc7c3e4906c7d21b961229d25f7f72b065841fa7ec",
+    "a07a85ab76ac3ef497f3143e203b7d1c760e3a8424d0d190745745298dab927f",
+    "e0cb54b009602d0d81bbb2b0a72f05e9e968bdcc914627f08b62a728c7c82d75",
+);
+
+pub const HASH_VECTORS: &[HashVector] = &[
+    HashVector {
+        input: b"",
+        digest: "0fac61934ad04bb4974d04a6a4b40824c4025fc01679a89e8a9ddc864af0634a",
+    },
+    HashVector {
+        input: b"a",
+        digest: "decd45d7d4f7a957aab539f3e5ae5115f781a841df063b8aa2e19ab2b6a564cd",
+    },
+    HashVector {
+        input: b"a\0",
+        digest: "d57371f546cf74592a70582a12ec899fb2b89832ead56e2c7285c27533bd0bee",
+    },
+    HashVector {
+        input: b"abc",
+        digest: "40618eaad3c1fe62063b8bfb13ba9db9a67bf64ead104ca2cdfd42ab6ba04dd9",
+    },
+    HashVector {
+        input: b"The quick brown fox jumps over the lazy dog",
+        digest: "99ced9ddb6a1bd38d2e57115eb51d592a7914244bacc0551816445cdf997d50c",
+    },
+    HashVector {
+        input: &[0x61; 200],
+        digest: "bdac94c6bd7e7e9defc071e3a6f016e100c88efc145b7d2f1ebe277a17124949",
+    },
+];
+
+/// Each non-default security level, and a doubled round count.
+pub const SECURE_HASH_VECTORS: &[SecureHashVector] = &[
+    SecureHashVector {
+        rounds: 64,
+        security_level: 128,
+        input: b"abc",
+        digest: "e4aba4e6e03a2c41ef61e7c567a00dc80cf4dad9b25d156db69190da532972b1",
+    },
+    SecureHashVector {
+        rounds: 64,
+        security_level: 512,
+        input: b"abc",
+        digest: "c36a51d54a7839173a72004ac9755d2e46dea678d6de45186edcd56fb90e55f0",
+    },
+    SecureHashVector {
+        rounds: 128,
+        security_level: 256,
+        input: b"abc",
+        digest: "8ae942c6e4dd5fff41ac49b46537a330bf26d04990405090c3aa288a84e35ce2",
+    },
+];
+
+pub const MAC_VECTORS: &[MacVector] = &[
+    MacVector {
+        key: b"key",
+        customization: b"",
+        input: b"",
+        tag: "fe205a492ed3dc1e28e1994caa48ab1d51e11887a7f595e939eeff5e71166fc8",
+    },
+    MacVector {
+        key: b"key",
+        customization: b"",
+        input: b"The quick brown fox jumps over the lazy dog",
+        tag: "ad8ec880970bc5e90cdb8e24a758204472690ecf8d09b282ede5d9ae5cbd2bba",
+    },
+    MacVector {
+        key: &[0x40; 32],
+        customization: b"My Tagged Application",
+        input: &[0x00, 0x01, 0x02, 0x03],
+        tag: "9189fdc58e6a46aeb1b578e329416d70f429a8dcf765eac150868e9a915f0e41",
+    },
+];
+
+pub const XOF_VECTORS: &[XofVector] = &[
+    XofVector {
+        input: b"",
+        output: "05293f09464ca954b70052ca75292f3344516e909a44facdbd5b9622b2fe4979c4ecc2ed1c8742b5983b8ba2ca94c0a3dbcdb9ae9493f3ef8c711534944822fb",
+    },
+    XofVector {
+        input: b"The quick brown fox jumps over the lazy dog",
+        output: "e7beda543486999e7b88a3672180d059cc8ad1d2913ade9ef62090ab4408e6dc11293e172141924a47ba531e70e45ac64ffcb0fc64c730dfa92f50024676b976",
+    },
+];
+
+pub const VARIANT_VECTORS: &[VariantVector] = &[
+    VariantVector {
+        bits: 224,
+        input: b"abc",
+        digest: "8c9a2f8ba2c8b60463b7e828f41ea66bdb6635722a52f6f938124b12",
+    },
+    VariantVector {
+        bits: 256,
+        input: b"abc",
+        digest: "40618eaad3c1fe62063b8bfb13ba9db9a67bf64ead104ca2cdfd42ab6ba04dd9",
+    },
+    VariantVector {
+        bits: 384,
+        input: b"abc",
+        digest: "5f3e7f803e229985cab3ae18f9891714855f9f6e8699ea324b3fd6dd2919af63dd0900c08823c813f67a63232e9ecd14",
+    },
+    VariantVector {
+        bits: 512,
+        input: b"abc",
+        digest: "ebbcd68c0a37defee61c8b9fed8a8cdc0f5e613efafc64421db60a088cf108865067b2b41611ffd3b9b7ec7be55e14794f543eec9a7a6f0076783b1246a3fb77",
+    },
+];
+
+/// The first two entries reuse the inputs of RFC 5869 test cases 3 and 1.
+pub const KDF_VECTORS: &[KdfVector] = &[
+    KdfVector {
+        salt: b"",
+        ikm: &[0x0b; 22],
+        info: b"",
+        okm: "7554a33392f76f491effa98acb9c658624c28f336b00858e707b4f56292b78c2d7112a30460fc3f7f71c",
+    },
+    KdfVector {
+        salt: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+        ],
+        ikm: &[0x0b; 22],
+        info: &[0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9],
+        okm: "e032ab3e030718e9e4b8251b7649eda7ebad02dc9d94605e61649313080f732321532acada7b81ee18c0",
+    },
+    KdfVector {
+        salt: b"salt",
+        ikm: b"input key material",
+        info: b"context",
+        okm: "669beda42aa99c7c5a44d8b976039f7c7a7371bd535c1a65bb51160a76d3e6b710c1faf1e3c15a57cd7d",
+    },
+];
+
+pub const PASSWORD_VECTORS: &[PasswordVector] = &[
+    PasswordVector {
+        password: b"password",
+        salt: b"somesalt",
+        m_cost: 64,
+        t_cost: 1,
+        p_cost: 1,
+        digest: "8c8cb751836a1e215b35e8ab30a751df7588a9cb0e4ec602db65b704b7e6b3e9",
+    },
+    PasswordVector {
+        password: b"password",
+        salt: b"somesalt",
+        m_cost: 64,
+        t_cost: 2,
+        p_cost: 2,
+        digest: "e71e7573a50fba33d9416fb4a667295edebe8ecdfd9699d9a6b248a4f8161718",
+    },
+    PasswordVector {
+        password: b"",
+        salt: &[0x02; 16],
+        m_cost: 32,
+        t_cost: 3,
+        p_cost: 4,
+        digest: "c639ce192d4642db7a59078758ce77b6420a7a317ea9c18a12a715042add537a",
+    },
+];
+
+/// Empty input, a single short chunk, exactly one chunk, one chunk plus a
+/// byte, and five chunks (an unbalanced tree).
+pub const TREE_VECTORS: &[TreeVector] = &[
+    TreeVector {
+        length: 0,
+        digest: "59849a9c1dac4bf297b0d955bf85ec56461ec37f46e1b228a8d110b4f9a47fb6",
+    },
+    TreeVector {
+        length: 1,
+        digest: "2022b93cef1ce3618a71fe06ce53a807690dc5bea502f34f23718c34bb204c0a",
+    },
+    TreeVector {
+        length: 8192,
+        digest: "da7f730722dfe0899b342a9a6d143b08dd6b802e08e6f9f803eb8f27e2a22db6",
+    },
+    TreeVector {
+        length: 8193,
+        digest: "4b4035972235d246abd801d151a6e505c2702ec0b350b958f43bff7eade0b38b",
+    },
+    TreeVector {
+        length: 40965,
+        digest: "32c68796377cbad945e94f369128b7df09aa86d0fa4c10ceaa9e423b0177ccc8",
+    },
+];
+
+/// Empty message and data, message only, associated data only, and a
+/// message longer than one rate block.
+pub const AEAD_VECTORS: &[AeadVector] = &[
+    AeadVector {
+        key: &[0x00; 32],
+        nonce: &[0x00; 16],
+        associated_data: b"",
+        plaintext: b"",
+        ciphertext: "495b7e04b906995a743847bcc4e35d5ff93eb8889b54c357d7e442cce41b011d",
+    },
+    AeadVector {
+        key: &[0x01; 32],
+        nonce: &[0x02; 16],
+        associated_data: b"",
+        plaintext: b"plaintext",
+        ciphertext: "ab6c239bbb567de223526590b7899258868d047429810927f6a51f00b10eb037be6adb7b46fb2afcc6",
+    },
+    AeadVector {
+        key: &[0x03; 32],
+        nonce: &[0x04; 16],
+        associated_data: b"associated data",
+        plaintext: b"",
+        ciphertext: "3f4be5f34533132c3a53efaafe3f9fd3956576d68f8a6f990ae3ce6ac5369cd2",
+    },
+    AeadVector {
+        key: &[0x05; 32],
+        nonce: &[0x06; 16],
+        associated_data: b"header",
+        plaintext: &[0x5a; 100],
+        ciphertext: "662a1174860c130793264fe079e36cc93c3a825241109100fae95194259e647191671c9579c79e974c3c7349936ab86bf8865bfd888df7180ff1c810e15a4113af34867b47df32f8b2136fa3eba7e026b5cd43d9631ea7c7c165bb4bdeee0e83524ecd3ba9bde013137ef6ba6d90b98c249bb1b59c4203a9571d70309f2dfa7840edd83f",
+    },
+];
+
+/// All-zero and all-`0x2a` seeds.
+pub const RNG_VECTORS: &[RngVector] = &[
+    RngVector {
+        seed: &[0x00; 32],
+        output: "32c93daa99aaaded1ce184ab7a040465af8867c4abdfe0c2e7ca0245fe5486fee97aacc243dfd362c6dfe002fca79c710cf3469451b7f7867450594a5ebe1161",
+    },
+    RngVector {
+        seed: &[0x2a; 32],
+        output: "494a0ed1934bd49b4a6d99a7d8bbd0b4632c6f2964da74733d6b4ffd1477e78d9464922c798f1790829b29973662525d094a8de45009e5fd34c00b41e547d0b9",
+    },
+];
@@ -0,0 +1,129 @@
+//! HKDF-style key derivation for VortexHash
+//!
+//! [`extract`] and [`expand`] follow the two-step structure of RFC 5869
+//! with [`VortexMac`] as the pseudorandom function:
+//!
+//! ```text
+//! PRK  = MAC(salt, IKM)
+//! T(0) = empty
+//! T(i) = MAC(PRK, T(i - 1) || info || i)        for i = 1..=N
+//! OKM  = first L bytes of T(1) || T(2) || ... || T(N)
+//! ```
+//!
+//! [`extract_with`] and [`expand_with`] run the same construction over any
+//! 32-byte RustCrypto MAC, which is how the implementation is checked
+//! against the RFC 5869 HMAC-SHA-256 test vectors.
+//!
+//! # Examples
+//!
+//! ```
+//! use vortex_hash::kdf;
+//!
+//! let prk = kdf::extract(b"salt", b"input key material");
+//! let mut key = [0u8; 32];
+//! kdf::expand(&prk, b"session key", &mut key).unwrap();
+//! ```
+
+use crate::mac::VortexMac;
+use digest::consts::U32;
+use digest::{KeyInit, Mac};
+use zeroize::Zeroize;
+
+/// Size of a pseudorandom key and of each expansion block.
+pub const PRK_SIZE: usize = 32;
+
+/// Longest output [`expand`] can produce (`255 * PRK_SIZE` bytes).
+pub const MAX_OUTPUT_SIZE: usize = 255 * PRK_SIZE;
+
+/// Pseudorandom key produced by [`extract`]; wiped when dropped.
+#[derive(Clone)]
+pub struct Prk([u8; PRK_SIZE]);
+
+impl Prk {
+    /// Wraps an existing pseudorandom key, for callers that already hold
+    /// uniformly random key material and skip [`extract`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let bytes: [u8; PRK_SIZE] = bytes.try_into().map_err(|_| "Invalid PRK length")?;
+        Ok(Self(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; PRK_SIZE] {
+        &self.0
+    }
+}
+
+impl Drop for Prk {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl std::fmt::Debug for Prk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Prk(..)")
+    }
+}
+
+/// Condenses `ikm` into a pseudorandom key, using `salt` as the MAC key.
+pub fn extract(salt: &[u8], ikm: &[u8]) -> Prk {
+    extract_with::<VortexMac>(salt, ikm)
+}
+
+/// Fills `okm` with key material bound to `info`.
+///
+/// Fails if `okm` is longer than [`MAX_OUTPUT_SIZE`] bytes.
+pub fn expand(prk: &Prk, info: &[u8], okm: &mut [u8]) -> Result<(), &'static str> {
+    expand_with::<VortexMac>(prk, info, okm)
+}
+
+/// [`extract`] over an arbitrary MAC with a 32-byte output.
+///
+/// An empty `salt` is replaced by [`PRK_SIZE`] zero bytes, as in RFC 5869.
+pub fn extract_with<M>(salt: &[u8], ikm: &[u8]) -> Prk
+where
+    M: Mac<OutputSize = U32> + KeyInit,
+{
+    let zero_salt = [0u8; PRK_SIZE];
+    let salt = if salt.is_empty() {
+        &zero_salt[..]
+    } else {
+        salt
+    };
+    let mut mac = <M as Mac>::new_from_slice(salt).expect("MAC accepts keys of any length");
+    mac.update(ikm);
+    let mut output = mac.finalize().into_bytes();
+    let prk = Prk(output.into());
+    output.zeroize();
+    prk
+}
+
+/// [`expand`] over an arbitrary MAC with a 32-byte output.
+pub fn expand_with<M>(prk: &Prk, info: &[u8], okm: &mut [u8]) -> Result<(), &'static str>
+where
+    M: Mac<OutputSize = U32> + KeyInit + Clone,
+{
+    if okm.len() > MAX_OUTPUT_SIZE {
+        return Err("Requested output too long");
+    }
+    let keyed = <M as Mac>::new_from_slice(&prk.0).expect("MAC accepts keys of any length");
+    let mut block = [0u8; PRK_SIZE];
+    for (i, chunk) in okm.chunks_mut(PRK_SIZE).enumerate() {
+        let mut mac = keyed.clone();
+        if i > 0 {
+            mac.update(&block);
+        }
+        mac.update(info);
+        mac.update(&[i as u8 + 1]);
+        let mut output = mac.finalize().into_bytes();
+        block.copy_from_slice(&output);
+        output.zeroize();
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+    block.zeroize();
+    Ok(())
+}
+
+/// One-shot [`extract`] followed by [`expand`].
+pub fn derive(salt: &[u8], ikm: &[u8], info: &[u8], okm: &mut [u8]) -> Result<(), &'static str> {
+    expand(&extract(salt, ikm), info, okm)
+}
@@ -1,28 +1,58 @@
+pub mod aead;
+pub mod bao;
+pub mod chunking;
 pub mod core;
 pub mod ecosystem;
 pub mod enterprise;
 pub mod hardware;
+pub mod hasher;
+pub mod kat;
+pub mod kdf;
+pub mod mac;
+pub mod merkle;
+pub mod password;
+pub mod permutation;
+pub mod rng;
 pub mod security;
+pub mod selftest;
+pub mod tree;
 pub mod utilities;
+pub mod variants;
 
 pub mod compatibility;
 pub mod fallback;
 pub mod migration;
 pub mod proofs;
 
+#[cfg(feature = "quantum")]
+pub mod sphincs;
+#[cfg(feature = "quantum")]
+pub(crate) mod thash;
+#[cfg(feature = "quantum")]
+pub mod wots;
+#[cfg(feature = "quantum")]
+pub mod xmss;
+
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
 #[cfg(feature = "std")]
 use std::string::String;
 
+pub use self::aead::VortexAead;
 pub use constant_time::*;
-pub use core::VortexHash;
+pub use core::{VortexHash, XofReader, ALGORITHM_VERSION};
 pub use ecosystem::*;
 pub use enterprise::*;
 pub use hardware::*;
+pub use hasher::{VortexBuildHasher, VortexFastBuildHasher, VortexHasher};
+pub use mac::VortexMac;
+pub use permutation::Permutation;
+pub use rng::VortexRng;
 pub use security::SecurityConfig;
+pub use tree::TreeHash;
 pub use utilities::*;
+pub use variants::{VortexHash224, VortexHash256, VortexHash384, VortexHash512};
 
 pub use compatibility::UniversalHash;
 pub use fallback::FallbackHash;
@@ -44,12 +74,15 @@ pub use core::VortexHash as VortexHashLegacy;
 /// ```
 #[inline(always)]
 pub fn hash(data: &[u8]) -> [u8; 32] {
-    let default_config = SecurityConfig::default();
-    hash_secure(data, &default_config)
+    VortexHash::hash(data)
 }
 
 /// Secure hash function with custom security configuration.
 ///
+/// The permutation runs `config.rounds` rounds and the sponge capacity is
+/// selected by `config.security_level`. Returns an error if `config` does
+/// not pass [`SecurityConfig::validate`].
+///
 /// # Examples
 ///
 /// ```
@@ -57,14 +90,41 @@ pub fn hash(data: &[u8]) -> [u8; 32] {
 ///
 /// let data = b"Secure data";
 /// let config = SecurityConfig::default();
-/// let result = hash_secure(data, &config);
+/// let result = hash_secure(data, &config).unwrap();
 /// assert_eq!(result.len(), 32);
+///
+/// let unchecked = SecurityConfig {
+///     constant_time: false,
+///     ..SecurityConfig::default()
+/// };
+/// assert!(hash_secure(data, &unchecked).is_err());
 /// ```
 #[inline(always)]
-pub fn hash_secure(data: &[u8], config: &SecurityConfig) -> [u8; 32] {
+pub fn hash_secure(data: &[u8], config: &SecurityConfig) -> Result<[u8; 32], &'static str> {
     core::hash_secure(data, config)
 }
 
+/// Extendable-output hash filling `output` with any number of bytes.
+///
+/// Uses the default security configuration and the XOF domain, so the first
+/// 32 bytes are unrelated to [`hash`] of the same input.
+///
+/// # Examples
+///
+/// ```
+/// use vortex_hash::hash_to;
+///
+/// let mut mask = [0u8; 1000];
+/// hash_to(b"seed", &mut mask);
+/// assert!(mask.iter().any(|&b| b != 0));
+/// ```
+#[inline(always)]
+pub fn hash_to(data: &[u8], output: &mut [u8]) {
+    let mut hasher = VortexHash::default();
+    hasher.absorb(data);
+    hasher.finalize_xof().squeeze(output);
+}
+
 /// Constant-time hash function for side-channel resistance.
 ///
 /// # Examples
@@ -79,8 +139,7 @@ pub fn hash_secure(data: &[u8], config: &SecurityConfig) -> [u8; 32] {
 #[inline(always)]
 pub fn hash_constant_time(data: &[u8]) -> [u8; 32] {
     use crate::constant_time::ct_eq;
-    let secure_config = SecurityConfig::default();
-    let secure_hash = hash_secure(data, &secure_config);
+    let secure_hash = hash(data);
     let validation = ct_eq(&secure_hash, &[0u8; 32]);
     if validation {
         secure_hash
@@ -91,17 +150,25 @@ pub fn hash_constant_time(data: &[u8]) -> [u8; 32] {
 
 /// Ultra-optimized hash function for high performance.
 ///
+/// Deprecated: this is [`hash`] under another name. Use [`TreeHash::digest`]
+/// to hash large inputs in parallel.
+///
 /// # Examples
 ///
 /// ```
+/// # #![allow(deprecated)]
 /// use vortex_hash::hash_ultra_optimized;
 ///
 /// let data = b"Ultra fast hash";
 /// let result = hash_ultra_optimized(data);
 /// assert_eq!(result.len(), 32);
 /// ```
+#[deprecated(
+    note = "same digest as `hash` with no acceleration; use `TreeHash::digest` to hash large inputs in parallel"
+)]
 #[inline(always)]
 pub fn hash_ultra_optimized(data: &[u8]) -> [u8; 32] {
+    #[allow(deprecated)]
     crate::hardware::UltraPerformance::hash_ultra_optimized(data)
 }
 
@@ -112,13 +179,10 @@ pub const MODULE_VERSION: &str = "0.1.0";
 
 pub const MODULE_COUNT: usize = 10;
 pub const ZERO_DOWNTIME_MIGRATION: bool = true;
-#[cfg(feature = "std")]
-pub const PERFORMANCE_IMPACT: f64 = 0.0;
-#[cfg(not(feature = "std"))]
-pub const PERFORMANCE_IMPACT: f64 = 0.0; // Placeholder for no_std
 pub const UNIVERSAL_COMPATIBILITY: bool = true;
 
-/// Check the health status of all modules.
+/// Runs the power-on self-tests of all modules (see [`selftest`]) and
+/// measures hashing throughput.
 ///
 /// # Examples
 ///
@@ -126,31 +190,36 @@ pub const UNIVERSAL_COMPATIBILITY: bool = true;
 /// use vortex_hash::health_check;
 ///
 /// let health = health_check();
-/// assert!(health.is_healthy());
+/// assert!(health.is_healthy(), "{:?}", health.failures());
 /// ```
 pub fn health_check() -> ModuleHealth {
+    let compatibility_module = selftest::check_compatibility();
+    let fallback_module = selftest::check_fallback();
     ModuleHealth {
-        core_module: true,
-        security_module: true,
-        hardware_module: true,
-        enterprise_module: true,
-        utilities_module: true,
-        proofs_module: true,
-        ecosystem_module: true,
-        compatibility_module: true,
-        migration_module: true,
-        fallback_module: true,
+        core_module: selftest::check_core(),
+        security_module: selftest::check_security(),
+        hardware_module: selftest::check_hardware(),
+        // Configuration only; nothing to test at runtime.
+        enterprise_module: Ok(()),
+        utilities_module: selftest::check_utilities(),
+        // Compile-time properties, checked by the test suite.
+        proofs_module: Ok(()),
+        ecosystem_module: selftest::check_ecosystem(),
+        migration_module: selftest::check_migration(),
+        universal_compatibility: compatibility_module.is_ok() && fallback_module.is_ok(),
+        compatibility_module,
+        fallback_module,
         total_modules: MODULE_COUNT,
         #[cfg(feature = "std")]
         migration_status: "Zero-downtime complete".to_string(),
         #[cfg(not(feature = "std"))]
         migration_status: alloc::format!("Zero-downtime complete"), // Use alloc for no_std
-        performance_impact: PERFORMANCE_IMPACT,
-        universal_compatibility: UNIVERSAL_COMPATIBILITY,
+        throughput_mib_s: selftest::measure_throughput(),
     }
 }
 
-/// Health status of all modules.
+/// Health status of all modules: `Ok(())` if a module passed its
+/// self-tests, or the reason it failed.
 ///
 /// # Examples
 ///
@@ -159,22 +228,24 @@ pub fn health_check() -> ModuleHealth {
 ///
 /// let health = health_check();
 /// println!("Total modules: {}", health.total_modules);
+/// println!("Throughput: {:.1} MiB/s", health.throughput_mib_s);
 /// ```
 #[derive(Debug, Clone)]
 pub struct ModuleHealth {
-    pub core_module: bool,
-    pub security_module: bool,
-    pub hardware_module: bool,
-    pub enterprise_module: bool,
-    pub utilities_module: bool,
-    pub proofs_module: bool,
-    pub ecosystem_module: bool,
-    pub compatibility_module: bool,
-    pub migration_module: bool,
-    pub fallback_module: bool,
+    pub core_module: Result<(), &'static str>,
+    pub security_module: Result<(), &'static str>,
+    pub hardware_module: Result<(), &'static str>,
+    pub enterprise_module: Result<(), &'static str>,
+    pub utilities_module: Result<(), &'static str>,
+    pub proofs_module: Result<(), &'static str>,
+    pub ecosystem_module: Result<(), &'static str>,
+    pub compatibility_module: Result<(), &'static str>,
+    pub migration_module: Result<(), &'static str>,
+    pub fallback_module: Result<(), &'static str>,
     pub total_modules: usize,
     pub migration_status: String,
-    pub performance_impact: f64,
+    /// Measured throughput of [`hash`] in MiB/s.
+    pub throughput_mib_s: f64,
     pub universal_compatibility: bool,
 }
 
@@ -189,35 +260,27 @@ impl ModuleHealth {
     /// let health = health_check();
     /// assert!(health.is_healthy());
     /// ```
-    #[cfg(feature = "std")]
     pub fn is_healthy(&self) -> bool {
-        self.core_module
-            && self.security_module
-            && self.hardware_module
-            && self.enterprise_module
-            && self.utilities_module
-            && self.proofs_module
-            && self.ecosystem_module
-            && self.compatibility_module
-            && self.migration_module
-            && self.fallback_module
-            && self.performance_impact < 0.1
-            && self.universal_compatibility
-    }
-
-    #[cfg(not(feature = "std"))]
-    pub fn is_healthy(&self) -> bool {
-        self.core_module
-            && self.security_module
-            && self.hardware_module
-            && self.enterprise_module
-            && self.utilities_module
-            && self.proofs_module
-            && self.ecosystem_module
-            && self.compatibility_module
-            && self.migration_module
-            && self.fallback_module
-            && self.universal_compatibility
+        self.failures().is_empty() && self.universal_compatibility
+    }
+
+    /// Names of the failed modules with the reason each one failed.
+    pub fn failures(&self) -> Vec<(&'static str, &'static str)> {
+        [
+            ("core", self.core_module),
+            ("security", self.security_module),
+            ("hardware", self.hardware_module),
+            ("enterprise", self.enterprise_module),
+            ("utilities", self.utilities_module),
+            ("proofs", self.proofs_module),
+            ("ecosystem", self.ecosystem_module),
+            ("compatibility", self.compatibility_module),
+            ("migration", self.migration_module),
+            ("fallback", self.fallback_module),
+        ]
+        .into_iter()
+        .filter_map(|(module, result)| result.err().map(|reason| (module, reason)))
+        .collect()
     }
 }
 
@@ -247,9 +310,32 @@ mod tests {
     #[test]
     fn test_module_health() {
         let health = health_check();
-        assert!(health.is_healthy());
+        assert!(health.is_healthy(), "{:?}", health.failures());
         assert_eq!(health.total_modules, MODULE_COUNT);
-        assert_eq!(health.performance_impact, 0.0);
+        assert!(health.throughput_mib_s > 0.0);
+    }
+
+    #[test]
+    fn test_module_health_reports_failures() {
+        let mut health = health_check();
+        health.fallback_module = Err("fallback backend KAT mismatch");
+        assert!(!health.is_healthy());
+        assert_eq!(
+            health.failures(),
+            vec![("fallback", "fallback backend KAT mismatch")]
+        );
+    }
+
+    #[test]
+    fn test_self_tests_pass() {
+        assert_eq!(selftest::check_core(), Ok(()));
+        assert_eq!(selftest::check_security(), Ok(()));
+        assert_eq!(selftest::check_hardware(), Ok(()));
+        assert_eq!(selftest::check_utilities(), Ok(()));
+        assert_eq!(selftest::check_ecosystem(), Ok(()));
+        assert_eq!(selftest::check_compatibility(), Ok(()));
+        assert_eq!(selftest::check_migration(), Ok(()));
+        assert_eq!(selftest::check_fallback(), Ok(()));
     }
 
     #[test]
@@ -295,6 +381,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_ultra_performance_hash() {
         use crate::hardware::UltraPerformance;
         let data = b"ultra test";
@@ -351,6 +438,43 @@ mod tests {
         assert!(config.validate());
     }
 
+    #[test]
+    fn test_security_config_rejects_invalid() {
+        use crate::SecurityConfig;
+        let mut config = SecurityConfig::new();
+        config.rounds = 0;
+        assert!(!config.validate());
+        assert!(VortexHash::new(&config).is_err());
+
+        let mut config = SecurityConfig::new();
+        config.security_level = 192;
+        assert!(!config.validate());
+        assert!(VortexHash::new(&config).is_err());
+    }
+
+    #[test]
+    fn test_security_config_default_is_valid() {
+        use crate::SecurityConfig;
+        let config = SecurityConfig::default();
+        assert!(config.validate());
+        assert_eq!(config.rounds, SecurityConfig::new().rounds);
+    }
+
+    #[test]
+    fn test_security_config_changes_output() {
+        use crate::SecurityConfig;
+        let data = b"config test";
+        let base = hash_secure(data, &SecurityConfig::new()).unwrap();
+        for level in [128, 512] {
+            let mut config = SecurityConfig::new();
+            config.security_level = level;
+            assert_ne!(hash_secure(data, &config).unwrap(), base);
+        }
+        let mut config = SecurityConfig::new();
+        config.rounds = 128;
+        assert_ne!(hash_secure(data, &config).unwrap(), base);
+    }
+
     #[test]
     fn test_security_config_display() {
         use crate::SecurityConfig;
@@ -374,6 +498,27 @@ mod tests {
         assert!(!validate_input(b""));
     }
 
+    #[test]
+    fn test_from_hex() {
+        use crate::{from_hex, to_hex};
+        let data = [0x00, 0x7f, 0x80, 0xab, 0xff];
+        assert_eq!(from_hex(&to_hex(&data)).unwrap(), data);
+        assert_eq!(from_hex("").unwrap(), Vec::<u8>::new());
+        assert!(from_hex("abc").is_err());
+        assert!(from_hex("AB").is_err());
+        assert!(from_hex("0g").is_err());
+    }
+
+    #[test]
+    fn test_parse_field() {
+        use crate::utilities::parse_field;
+        assert_eq!(parse_field("m=0", "m"), Ok(0));
+        assert_eq!(parse_field("m=4096", "m"), Ok(4096));
+        for field in ["m=", "m=01", "m=+1", "m=-1", "t=1", "m1", "m=4294967296"] {
+            assert!(parse_field(field, "m").is_err(), "{}", field);
+        }
+    }
+
     #[test]
     fn test_constant_time_ct_eq() {
         use crate::utilities::constant_time::ct_eq;
@@ -388,12 +533,29 @@ mod tests {
     fn test_vortex_hash_new() {
         use crate::{SecurityConfig, VortexHash};
         let config = SecurityConfig::default();
-        let mut hasher = VortexHash::new(&config);
+        let mut hasher = VortexHash::new(&config).unwrap();
         hasher.absorb(b"test data");
         let result = hasher.squeeze();
         assert_eq!(result.len(), 32);
     }
 
+    #[test]
+    fn test_trailing_zero_bytes_do_not_collide() {
+        assert_ne!(hash(b"a"), hash(b"a\0"));
+        assert_ne!(hash(b""), hash(b"\0"));
+        assert_ne!(hash(&[0u8; 31]), hash(&[0u8; 32]));
+        assert_ne!(hash(&[0u8; 32]), hash(&[0u8; 33]));
+    }
+
+    #[test]
+    fn test_modes_are_domain_separated() {
+        let key = b"key";
+        let data = b"data";
+        let mut combined = key.to_vec();
+        combined.extend_from_slice(data);
+        assert_ne!(VortexMac::mac(key, data), hash(&combined));
+    }
+
     #[test]
     fn test_hash_empty_data() {
         let result = hash(b"");
@@ -402,10 +564,33 @@ mod tests {
 
     #[test]
     fn test_hash_secure_empty_data() {
-        let result = hash_secure(b"", &SecurityConfig::default());
+        let result = hash_secure(b"", &SecurityConfig::default()).unwrap();
         assert_eq!(result.len(), 32);
     }
 
+    #[test]
+    fn test_hash_secure_rejects_invalid_config() {
+        let mut output = [0u8; 16];
+        for config in [
+            SecurityConfig {
+                constant_time: false,
+                ..SecurityConfig::default()
+            },
+            SecurityConfig {
+                security_level: 192,
+                ..SecurityConfig::default()
+            },
+            SecurityConfig {
+                rounds: 0,
+                ..SecurityConfig::default()
+            },
+        ] {
+            assert!(hash_secure(b"data", &config).is_err());
+            assert!(VortexHash::hash_secure(b"data", &config).is_err());
+            assert!(core::hash_to_secure(b"data", &config, &mut output).is_err());
+        }
+    }
+
     #[test]
     fn test_hash_constant_time_empty() {
         let result = hash_constant_time(b"");
@@ -413,6 +598,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_hash_ultra_optimized_empty() {
         let result = hash_ultra_optimized(b"");
         assert_eq!(result.len(), 32);
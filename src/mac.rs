@@ -0,0 +1,173 @@
+//! Keyed message authentication for VortexHash
+//!
+//! `VortexMac` is a KMAC-style sponge MAC. The sponge absorbs
+//!
+//! ```text
+//! bytepad(encode_string(customization), rate)
+//! || bytepad(encode_string(key), rate)
+//! || message
+//! || right_encode(tag_bits)
+//! ```
+//!
+//! and is finalized with the [`domain::MAC`] byte. Framing the key with its
+//! length keeps `(key, message)` pairs unambiguous, and encoding the tag
+//! length makes a truncated tag unrelated to a prefix of a longer one.
+
+use crate::core::{domain, VortexHash, DEFAULT_CAPACITY, STATE_SIZE};
+use crate::security::DEFAULT_ROUNDS;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// Default tag size in bytes.
+pub const TAG_SIZE: usize = 32;
+/// Shortest tag accepted by [`VortexMac::finalize_into`] and
+/// [`VortexMac::verify`].
+pub const MIN_TAG_SIZE: usize = 16;
+/// Longest tag accepted by [`VortexMac::finalize_into`] and
+/// [`VortexMac::verify`].
+pub const MAX_TAG_SIZE: usize = 64;
+
+/// Streaming keyed MAC over the VortexHash sponge.
+///
+/// # Examples
+///
+/// ```
+/// use vortex_hash::VortexMac;
+///
+/// let mut mac = VortexMac::new(b"secret key");
+/// mac.update(b"message ");
+/// mac.update(b"to authenticate");
+/// let tag = mac.finalize();
+///
+/// let mut check = VortexMac::new(b"secret key");
+/// check.update(b"message to authenticate");
+/// assert!(check.verify(&tag));
+/// ```
+#[derive(Clone)]
+pub struct VortexMac {
+    sponge: VortexHash,
+    keyed: VortexHash, // State right after keying, restored by `reset`
+}
+
+impl VortexMac {
+    /// Creates a MAC keyed with `key` and an empty customization string.
+    pub fn new(key: &[u8]) -> Self {
+        Self::with_customization(key, b"")
+    }
+
+    /// Creates a MAC keyed with `key` for the application-chosen
+    /// `customization` string. Tags computed under different
+    /// customization strings are independent.
+    pub fn with_customization(key: &[u8], customization: &[u8]) -> Self {
+        let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+        let rate = sponge.rate();
+        absorb_bytepad(&mut sponge, customization, rate);
+        absorb_bytepad(&mut sponge, key, rate);
+        Self {
+            keyed: sponge.clone(),
+            sponge,
+        }
+    }
+
+    /// One-shot [`TAG_SIZE`]-byte tag of `data` under `key`.
+    pub fn mac(key: &[u8], data: &[u8]) -> [u8; TAG_SIZE] {
+        let mut mac = Self::new(key);
+        mac.update(data);
+        mac.finalize()
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.sponge.absorb(data);
+    }
+
+    /// Returns a [`TAG_SIZE`]-byte tag.
+    pub fn finalize(self) -> [u8; TAG_SIZE] {
+        let mut tag = [0u8; TAG_SIZE];
+        self.finalize_tag(&mut tag);
+        tag
+    }
+
+    /// Writes a tag of `tag.len()` bytes, which must be between
+    /// [`MIN_TAG_SIZE`] and [`MAX_TAG_SIZE`].
+    pub fn finalize_into(self, tag: &mut [u8]) -> Result<(), &'static str> {
+        if !(MIN_TAG_SIZE..=MAX_TAG_SIZE).contains(&tag.len()) {
+            return Err("Unsupported tag length");
+        }
+        self.finalize_tag(tag);
+        Ok(())
+    }
+
+    /// Checks `tag` in constant time. Truncated tags are verified at their
+    /// own length; tags outside [`MIN_TAG_SIZE`]..=[`MAX_TAG_SIZE`] are
+    /// rejected.
+    pub fn verify(self, tag: &[u8]) -> bool {
+        if !(MIN_TAG_SIZE..=MAX_TAG_SIZE).contains(&tag.len()) {
+            return false;
+        }
+        let mut expected = [0u8; MAX_TAG_SIZE];
+        let expected = &mut expected[..tag.len()];
+        self.finalize_tag(expected);
+        let equal = expected.ct_eq(tag).into();
+        expected.zeroize();
+        equal
+    }
+
+    /// Discards all input, keeping the key and customization string.
+    pub fn reset(&mut self) {
+        self.sponge = self.keyed.clone();
+    }
+
+    fn finalize_tag(mut self, tag: &mut [u8]) {
+        self.finalize_tag_mut(tag);
+    }
+
+    pub(crate) fn finalize_tag_mut(&mut self, tag: &mut [u8]) {
+        let mut length = [0u8; 9];
+        let encoded = right_encode((tag.len() as u64) * 8, &mut length);
+        self.sponge.absorb(encoded);
+        self.sponge.finalize_into(domain::MAC, tag);
+    }
+}
+
+impl Drop for VortexMac {
+    fn drop(&mut self) {
+        self.sponge.zeroize();
+        self.keyed.zeroize();
+    }
+}
+
+/// Absorbs `bytepad(encode_string(data), rate)`.
+fn absorb_bytepad(sponge: &mut VortexHash, data: &[u8], rate: usize) {
+    let mut rate_buf = [0u8; 9];
+    let mut length_buf = [0u8; 9];
+    let prefix = left_encode(rate as u64, &mut rate_buf);
+    let length = left_encode((data.len() as u64) * 8, &mut length_buf);
+    sponge.absorb(prefix);
+    sponge.absorb(length);
+    sponge.absorb(data);
+    let written = prefix.len() + length.len() + data.len();
+    let padding = (rate - written % rate) % rate;
+    sponge.absorb(&[0u8; STATE_SIZE][..padding]);
+}
+
+/// `left_encode(x)`: the big-endian bytes of `x` without leading zeros,
+/// preceded by their count.
+pub(crate) fn left_encode(x: u64, buf: &mut [u8; 9]) -> &[u8] {
+    let bytes = x.to_be_bytes();
+    let skip = (x.leading_zeros() as usize / 8).min(7);
+    let n = 8 - skip;
+    buf[0] = n as u8;
+    buf[1..=n].copy_from_slice(&bytes[skip..]);
+    &buf[..=n]
+}
+
+/// `right_encode(x)`: the big-endian bytes of `x` without leading zeros,
+/// followed by their count.
+pub(crate) fn right_encode(x: u64, buf: &mut [u8; 9]) -> &[u8] {
+    let bytes = x.to_be_bytes();
+    let skip = (x.leading_zeros() as usize / 8).min(7);
+    let n = 8 - skip;
+    buf[..n].copy_from_slice(&bytes[skip..]);
+    buf[n] = n as u8;
+    &buf[..=n]
+}
@@ -0,0 +1,632 @@
+//! Merkle trees for audit logs and key-value state
+//!
+//! [`MerkleTree`] is an append-only log with RFC 6962 semantics: the tree
+//! over `n` leaves splits them at the largest power of two below `n`,
+//! and it produces [`InclusionProof`]s for single entries and
+//! [`ConsistencyProof`]s showing that one tree size is a prefix of a
+//! larger one. [`SparseMerkleTree`] is a 256-level tree indexed by the
+//! hash of a key, with proofs of both presence and absence.
+//!
+//! Hashes are VortexHash at the 256-bit security level. Leaves, interior
+//! nodes and the empty tree are finalized with [`domain::MERKLE_LEAF`],
+//! [`domain::MERKLE_NODE`] and [`domain::MERKLE_EMPTY`], taking the place
+//! of the `0x00` and `0x01` prefixes of RFC 6962, so a leaf can never be
+//! passed off as an interior node.
+//!
+//! # Proof encoding
+//!
+//! ```text
+//! InclusionProof:   be64(index) || be64(tree size) || path (32 each)
+//! ConsistencyProof: be64(old size) || be64(new size) || path (32 each)
+//! SparseProof:      bitmap (32) || non-empty siblings (32 each)
+//! ```
+//!
+//! Paths are ordered from the leaf upwards. Bit `i` of the sparse proof
+//! bitmap (least significant bit of byte 0 first) is set when the sibling
+//! at height `i` is not the root of an empty subtree; only those siblings
+//! are stored.
+//!
+//! # Examples
+//!
+//! ```
+//! use vortex_hash::merkle::MerkleTree;
+//!
+//! let mut log = MerkleTree::new();
+//! for entry in [&b"first"[..], b"second", b"third"] {
+//!     log.push(entry);
+//! }
+//! let root = log.root();
+//! let proof = log.inclusion_proof(1, log.len()).unwrap();
+//! assert!(proof.verify(b"second", &root));
+//! ```
+
+use crate::core::{domain, VortexHash, DEFAULT_CAPACITY};
+use crate::security::DEFAULT_ROUNDS;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+/// Size of a node hash in bytes.
+pub const HASH_SIZE: usize = 32;
+
+/// Height of a [`SparseMerkleTree`] (bits in a key hash).
+pub const SPARSE_DEPTH: usize = 256;
+
+/// Longest path accepted when decoding a log proof.
+const MAX_PATH: usize = 64;
+
+pub type Hash = [u8; HASH_SIZE];
+
+fn finalize(inputs: &[&[u8]], domain: u8) -> Hash {
+    let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+    for input in inputs {
+        sponge.absorb(input);
+    }
+    let mut output = [0u8; HASH_SIZE];
+    sponge.finalize_into(domain, &mut output);
+    output
+}
+
+/// Hash of a leaf holding `data`.
+pub fn leaf_hash(data: &[u8]) -> Hash {
+    finalize(&[data], domain::MERKLE_LEAF)
+}
+
+/// Hash of the interior node with children `left` and `right`.
+pub fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    finalize(&[left, right], domain::MERKLE_NODE)
+}
+
+/// Root of the tree without leaves.
+pub fn empty_root() -> Hash {
+    finalize(&[], domain::MERKLE_EMPTY)
+}
+
+/// Largest power of two strictly below `n`, for `n > 1`.
+fn split_point(n: usize) -> usize {
+    1 << (usize::BITS - 1 - (n - 1).leading_zeros())
+}
+
+/// Append-only Merkle tree over a log of entries.
+///
+/// The roots of complete subtrees are stored as leaves are appended, so a
+/// root or proof costs `O(log n)` node hashes for the incomplete subtrees
+/// on the right edge instead of rehashing the whole tree. The current root
+/// is kept until the next append.
+#[derive(Debug, Clone, Default)]
+pub struct MerkleTree {
+    /// `levels[h][i]` is the root of the complete subtree over leaves
+    /// `i << h .. (i + 1) << h`; `levels[0]` holds the leaf hashes.
+    levels: Vec<Vec<Hash>>,
+    root: OnceLock<Hash>,
+}
+
+impl MerkleTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of leaves.
+    pub fn len(&self) -> u64 {
+        self.leaf_count() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaf_count() == 0
+    }
+
+    fn leaf_count(&self) -> usize {
+        self.levels.first().map_or(0, Vec::len)
+    }
+
+    /// Appends an entry and returns its index.
+    pub fn push(&mut self, data: &[u8]) -> u64 {
+        self.push_leaf_hash(leaf_hash(data))
+    }
+
+    /// Appends a precomputed [`leaf_hash`] and returns its index.
+    pub fn push_leaf_hash(&mut self, leaf: Hash) -> u64 {
+        let index = self.len();
+        let mut node = leaf;
+        for height in 0.. {
+            if height == self.levels.len() {
+                self.levels.push(Vec::new());
+            }
+            let level = &mut self.levels[height];
+            level.push(node);
+            if level.len() % 2 == 1 {
+                break;
+            }
+            node = node_hash(&level[level.len() - 2], &level[level.len() - 1]);
+        }
+        self.root.take();
+        index
+    }
+
+    /// Root of the current tree.
+    pub fn root(&self) -> Hash {
+        *self
+            .root
+            .get_or_init(|| self.subtree_root(0, self.leaf_count()))
+    }
+
+    /// Root of the tree as it was when it had `size` leaves.
+    pub fn root_at(&self, size: u64) -> Result<Hash, &'static str> {
+        let size = self.check_size(size)?;
+        Ok(self.subtree_root(0, size))
+    }
+
+    /// Proof that the leaf at `index` is in the tree of `size` leaves.
+    pub fn inclusion_proof(&self, index: u64, size: u64) -> Result<InclusionProof, &'static str> {
+        let n = self.check_size(size)?;
+        if index >= size {
+            return Err("Leaf index out of range");
+        }
+        let mut path = Vec::new();
+        self.inclusion_path(index as usize, 0, n, &mut path);
+        Ok(InclusionProof { index, size, path })
+    }
+
+    /// Proof that the tree of `old_size` leaves is a prefix of the tree of
+    /// `new_size` leaves.
+    pub fn consistency_proof(
+        &self,
+        old_size: u64,
+        new_size: u64,
+    ) -> Result<ConsistencyProof, &'static str> {
+        let n = self.check_size(new_size)?;
+        if old_size > new_size {
+            return Err("Old size exceeds new size");
+        }
+        let mut path = Vec::new();
+        if old_size > 0 {
+            self.consistency_path(old_size as usize, 0, n, true, &mut path);
+        }
+        Ok(ConsistencyProof {
+            old_size,
+            new_size,
+            path,
+        })
+    }
+
+    /// `MTH` of RFC 6962 over the `n` leaves from `start`. Every complete
+    /// subtree reached from the root is aligned to its size, so it is read
+    /// from `levels`.
+    fn subtree_root(&self, start: usize, n: usize) -> Hash {
+        match n {
+            0 => empty_root(),
+            n if n.is_power_of_two() => {
+                let height = n.trailing_zeros() as usize;
+                self.levels[height][start >> height]
+            }
+            n => {
+                let k = split_point(n);
+                node_hash(
+                    &self.subtree_root(start, k),
+                    &self.subtree_root(start + k, n - k),
+                )
+            }
+        }
+    }
+
+    /// `PATH(m, D[n])` of RFC 6962 over the `n` leaves from `start`, leaf
+    /// first.
+    fn inclusion_path(&self, index: usize, start: usize, n: usize, path: &mut Vec<Hash>) {
+        if n <= 1 {
+            return;
+        }
+        let k = split_point(n);
+        if index < k {
+            self.inclusion_path(index, start, k, path);
+            path.push(self.subtree_root(start + k, n - k));
+        } else {
+            self.inclusion_path(index - k, start + k, n - k, path);
+            path.push(self.subtree_root(start, k));
+        }
+    }
+
+    /// `SUBPROOF(m, D[n], b)` of RFC 6962 over the `n` leaves from `start`.
+    fn consistency_path(
+        &self,
+        m: usize,
+        start: usize,
+        n: usize,
+        complete: bool,
+        path: &mut Vec<Hash>,
+    ) {
+        if m == n {
+            if !complete {
+                path.push(self.subtree_root(start, n));
+            }
+            return;
+        }
+        let k = split_point(n);
+        if m <= k {
+            self.consistency_path(m, start, k, complete, path);
+            path.push(self.subtree_root(start + k, n - k));
+        } else {
+            self.consistency_path(m - k, start + k, n - k, false, path);
+            path.push(self.subtree_root(start, k));
+        }
+    }
+
+    fn check_size(&self, size: u64) -> Result<usize, &'static str> {
+        if size > self.len() {
+            Err("Tree size exceeds number of leaves")
+        } else {
+            Ok(size as usize)
+        }
+    }
+}
+
+fn encode_path(a: u64, b: u64, path: &[Hash]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(16 + path.len() * HASH_SIZE);
+    bytes.extend_from_slice(&a.to_be_bytes());
+    bytes.extend_from_slice(&b.to_be_bytes());
+    for hash in path {
+        bytes.extend_from_slice(hash);
+    }
+    bytes
+}
+
+fn decode_path(bytes: &[u8]) -> Result<(u64, u64, Vec<Hash>), &'static str> {
+    if bytes.len() < 16 || !(bytes.len() - 16).is_multiple_of(HASH_SIZE) {
+        return Err("Invalid proof length");
+    }
+    let path: Vec<Hash> = bytes[16..]
+        .chunks_exact(HASH_SIZE)
+        .map(|hash| hash.try_into().unwrap())
+        .collect();
+    if path.len() > MAX_PATH {
+        return Err("Invalid proof length");
+    }
+    let a = u64::from_be_bytes(bytes[..8].try_into().unwrap());
+    let b = u64::from_be_bytes(bytes[8..16].try_into().unwrap());
+    Ok((a, b, path))
+}
+
+/// Proof that a leaf is included in a tree of a given size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InclusionProof {
+    index: u64,
+    size: u64,
+    path: Vec<Hash>,
+}
+
+impl InclusionProof {
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    pub fn tree_size(&self) -> u64 {
+        self.size
+    }
+
+    /// Returns `true` if `data` is the entry at [`Self::index`] of the tree
+    /// with root `root`.
+    pub fn verify(&self, data: &[u8], root: &Hash) -> bool {
+        self.verify_leaf_hash(&leaf_hash(data), root)
+    }
+
+    /// Same as [`Self::verify`] for a precomputed [`leaf_hash`]
+    /// (RFC 9162, section 2.1.3.2).
+    pub fn verify_leaf_hash(&self, leaf: &Hash, root: &Hash) -> bool {
+        if self.index >= self.size {
+            return false;
+        }
+        let mut node = self.index;
+        let mut last = self.size - 1;
+        let mut r = *leaf;
+        for p in &self.path {
+            if last == 0 {
+                return false;
+            }
+            if node & 1 == 1 || node == last {
+                r = node_hash(p, &r);
+                while node & 1 == 0 && node != 0 {
+                    node >>= 1;
+                    last >>= 1;
+                }
+            } else {
+                r = node_hash(&r, p);
+            }
+            node >>= 1;
+            last >>= 1;
+        }
+        last == 0 && r == *root
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode_path(self.index, self.size, &self.path)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let (index, size, path) = decode_path(bytes)?;
+        Ok(Self { index, size, path })
+    }
+}
+
+/// Proof that one tree size is a prefix of a larger one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsistencyProof {
+    old_size: u64,
+    new_size: u64,
+    path: Vec<Hash>,
+}
+
+impl ConsistencyProof {
+    pub fn old_size(&self) -> u64 {
+        self.old_size
+    }
+
+    pub fn new_size(&self) -> u64 {
+        self.new_size
+    }
+
+    /// Returns `true` if the tree with root `old_root` and
+    /// [`Self::old_size`] leaves is a prefix of the tree with root
+    /// `new_root` (RFC 9162, section 2.1.4.2).
+    pub fn verify(&self, old_root: &Hash, new_root: &Hash) -> bool {
+        if self.old_size > self.new_size {
+            return false;
+        }
+        if self.old_size == self.new_size {
+            return self.path.is_empty() && old_root == new_root;
+        }
+        if self.old_size == 0 {
+            return self.path.is_empty() && *old_root == empty_root();
+        }
+
+        let mut path = self.path.iter();
+        let first = if self.old_size.is_power_of_two() {
+            old_root
+        } else {
+            match path.next() {
+                Some(first) => first,
+                None => return false,
+            }
+        };
+        let mut node = self.old_size - 1;
+        let mut last = self.new_size - 1;
+        while node & 1 == 1 {
+            node >>= 1;
+            last >>= 1;
+        }
+        let mut fr = *first;
+        let mut sr = *first;
+        for c in path {
+            if last == 0 {
+                return false;
+            }
+            if node & 1 == 1 || node == last {
+                fr = node_hash(c, &fr);
+                sr = node_hash(c, &sr);
+                while node & 1 == 0 && node != 0 {
+                    node >>= 1;
+                    last >>= 1;
+                }
+            } else {
+                sr = node_hash(&sr, c);
+            }
+            node >>= 1;
+            last >>= 1;
+        }
+        fr == *old_root && sr == *new_root && last == 0
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode_path(self.old_size, self.new_size, &self.path)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let (old_size, new_size, path) = decode_path(bytes)?;
+        Ok(Self {
+            old_size,
+            new_size,
+            path,
+        })
+    }
+}
+
+/// Roots of empty sparse subtrees, indexed by height.
+fn empty_subtrees() -> &'static [Hash; SPARSE_DEPTH + 1] {
+    static EMPTY: OnceLock<[Hash; SPARSE_DEPTH + 1]> = OnceLock::new();
+    EMPTY.get_or_init(|| {
+        let mut empty = [[0u8; HASH_SIZE]; SPARSE_DEPTH + 1];
+        empty[0] = empty_root();
+        for height in 1..=SPARSE_DEPTH {
+            empty[height] = node_hash(&empty[height - 1], &empty[height - 1]);
+        }
+        empty
+    })
+}
+
+/// Position of `key` in a sparse tree.
+fn key_path(key: &[u8]) -> Hash {
+    crate::hash(key)
+}
+
+/// Bit `depth` of `path`, counted from the most significant bit; `true`
+/// selects the right child at that depth.
+fn path_bit(path: &Hash, depth: usize) -> bool {
+    path[depth / 8] & (0x80 >> (depth % 8)) != 0
+}
+
+fn sparse_leaf(path: &Hash, value: &[u8]) -> Hash {
+    finalize(&[path, value], domain::MERKLE_LEAF)
+}
+
+/// Root of the sparse subtree at `depth` holding `leaves`, which are
+/// sorted by path.
+fn sparse_root(leaves: &[(Hash, Hash)], depth: usize) -> Hash {
+    if leaves.is_empty() {
+        return empty_subtrees()[SPARSE_DEPTH - depth];
+    }
+    if depth == SPARSE_DEPTH {
+        return leaves[0].1;
+    }
+    let split = leaves.partition_point(|(path, _)| !path_bit(path, depth));
+    node_hash(
+        &sparse_root(&leaves[..split], depth + 1),
+        &sparse_root(&leaves[split..], depth + 1),
+    )
+}
+
+/// Key-value map authenticated by a 256-level sparse Merkle tree.
+///
+/// Each key is placed at the leaf selected by its [`crate::hash`], which
+/// holds the hash of that path and the value; every other leaf is empty.
+/// Leaf hashes are computed on insertion and the root is kept until the
+/// next write.
+///
+/// # Examples
+///
+/// ```
+/// use vortex_hash::merkle::SparseMerkleTree;
+///
+/// let mut state = SparseMerkleTree::new();
+/// state.insert(b"alice", b"100");
+/// let root = state.root();
+///
+/// let proof = state.prove(b"alice");
+/// assert!(proof.verify(&root, b"alice", Some(b"100")));
+/// let absent = state.prove(b"bob");
+/// assert!(absent.verify(&root, b"bob", None));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SparseMerkleTree {
+    entries: BTreeMap<Hash, (Vec<u8>, Hash)>, // Path -> value, leaf hash
+    root: OnceLock<Hash>,
+}
+
+impl SparseMerkleTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Sets the value of `key`, returning the previous value.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> Option<Vec<u8>> {
+        let path = key_path(key);
+        let leaf = sparse_leaf(&path, value);
+        self.root.take();
+        self.entries
+            .insert(path, (value.to_vec(), leaf))
+            .map(|(value, _)| value)
+    }
+
+    /// Removes `key`, returning its value.
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        let (value, _) = self.entries.remove(&key_path(key))?;
+        self.root.take();
+        Some(value)
+    }
+
+    pub fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        self.entries
+            .get(&key_path(key))
+            .map(|(value, _)| value.as_slice())
+    }
+
+    fn leaves(&self) -> Vec<(Hash, Hash)> {
+        self.entries
+            .iter()
+            .map(|(path, (_, leaf))| (*path, *leaf))
+            .collect()
+    }
+
+    pub fn root(&self) -> Hash {
+        *self.root.get_or_init(|| sparse_root(&self.leaves(), 0))
+    }
+
+    /// Proof of the value of `key`, or of its absence.
+    pub fn prove(&self, key: &[u8]) -> SparseProof {
+        let path = key_path(key);
+        let leaves = self.leaves();
+        let mut remaining = &leaves[..];
+        let mut siblings = vec![None; SPARSE_DEPTH];
+        for depth in 0..SPARSE_DEPTH {
+            let split = remaining.partition_point(|(p, _)| !path_bit(p, depth));
+            let (left, right) = remaining.split_at(split);
+            let (own, other) = if path_bit(&path, depth) {
+                (right, left)
+            } else {
+                (left, right)
+            };
+            if !other.is_empty() {
+                siblings[SPARSE_DEPTH - 1 - depth] = Some(sparse_root(other, depth + 1));
+            }
+            remaining = own;
+        }
+        SparseProof { siblings }
+    }
+}
+
+/// Proof of presence or absence of a key in a [`SparseMerkleTree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseProof {
+    /// Sibling at each height, leaf first; `None` for empty subtrees.
+    siblings: Vec<Option<Hash>>,
+}
+
+impl SparseProof {
+    /// Returns `true` if, in the tree with root `root`, `key` maps to
+    /// `value`, or is absent when `value` is `None`.
+    pub fn verify(&self, root: &Hash, key: &[u8], value: Option<&[u8]>) -> bool {
+        let empty = empty_subtrees();
+        let path = key_path(key);
+        let mut node = match value {
+            Some(value) => sparse_leaf(&path, value),
+            None => empty[0],
+        };
+        for (height, sibling) in self.siblings.iter().enumerate() {
+            let sibling = sibling.as_ref().unwrap_or(&empty[height]);
+            node = if path_bit(&path, SPARSE_DEPTH - 1 - height) {
+                node_hash(sibling, &node)
+            } else {
+                node_hash(&node, sibling)
+            };
+        }
+        node == *root
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bitmap = [0u8; SPARSE_DEPTH / 8];
+        let mut hashes = Vec::new();
+        for (height, sibling) in self.siblings.iter().enumerate() {
+            if let Some(sibling) = sibling {
+                bitmap[height / 8] |= 1 << (height % 8);
+                hashes.extend_from_slice(sibling);
+            }
+        }
+        let mut bytes = bitmap.to_vec();
+        bytes.extend_from_slice(&hashes);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let bitmap_size = SPARSE_DEPTH / 8;
+        if bytes.len() < bitmap_size {
+            return Err("Invalid proof length");
+        }
+        let (bitmap, hashes) = bytes.split_at(bitmap_size);
+        let present: u32 = bitmap.iter().map(|byte| byte.count_ones()).sum();
+        if hashes.len() != present as usize * HASH_SIZE {
+            return Err("Invalid proof length");
+        }
+        let mut hashes = hashes.chunks_exact(HASH_SIZE);
+        let siblings = (0..SPARSE_DEPTH)
+            .map(|height| {
+                (bitmap[height / 8] & (1 << (height % 8)) != 0)
+                    .then(|| hashes.next().unwrap().try_into().unwrap())
+            })
+            .collect();
+        Ok(Self { siblings })
+    }
+}
@@ -1,6 +1,66 @@
 //! Migration helpers for VortexHash
+//!
+//! [`VersionedDigest`] is a self-describing digest for storage: it records
+//! the algorithm, the [`ALGORITHM_VERSION`] of a VortexHash digest, the
+//! [`SecurityConfig`] parameters of a [`crate::hash_secure`] digest, and
+//! the digest bytes, so stored hashes from different algorithms can live
+//! side by side and be verified without out-of-band metadata. Digests from
+//! another version of the VortexHash construction are rejected when parsed,
+//! since this build cannot recompute them.
+//!
+//! The binary encoding is a multihash:
+//!
+//! ```text
+//! varint(code) || varint(length) || payload
+//! ```
+//!
+//! with unsigned LEB128 varints of at most 9 bytes, minimally encoded. For
+//! an algorithm from [`crate::compatibility::REGISTRY`] the code is its
+//! multicodec and the payload is the digest, so legacy encodings are plain
+//! multihashes. VortexHash payloads start with `varint(version)`. A
+//! VortexHash-256 digest with parameters uses the private code
+//! [`PARAMETERIZED_CODE`] and the payload
+//! `varint(version) || varint(rounds) || varint(security_level) || digest`.
+//!
+//! The string encoding follows the PHC layout of [`crate::password`], with
+//! the digest in lowercase hex:
+//!
+//! ```text
+//! $<algorithm>$<digest>
+//! $vortex-<bits>$v=<version>$<digest>
+//! $vortex-256$v=<version>$r=<rounds>,l=<security_level>$<digest>
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use vortex_hash::compatibility::Algorithm;
+//! use vortex_hash::migration::VersionedDigest;
+//!
+//! let stored = VersionedDigest::compute(Algorithm::Sha256, b"data").to_string();
+//! let digest: VersionedDigest = stored.parse().unwrap();
+//! assert!(digest.verify(b"data"));
+//! ```
 
+use crate::compatibility::Algorithm;
+use crate::core::{hash_secure, ALGORITHM_VERSION};
+use crate::security::SecurityConfig;
+use crate::utilities::{from_hex, parse_field, to_hex};
 use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+use subtle::ConstantTimeEq;
+
+/// Multihash code of a VortexHash-256 digest with parameters.
+pub const PARAMETERIZED_CODE: u64 = 0x30_0100;
+
+/// Largest `rounds` accepted in [`DigestParams`], sixteen times the
+/// default, so a stored digest cannot make [`VersionedDigest::verify`] run
+/// an unbounded number of permutation rounds.
+pub const MAX_ROUNDS: u32 = 1024;
+
+/// Longest unsigned varint accepted, as in the multiformats specification.
+const MAX_VARINT_LEN: usize = 9;
 
 pub struct MigrationHelper;
 
@@ -18,3 +78,281 @@ impl MigrationHelper {
 pub fn is_migration_needed() -> bool {
     false // Placeholder
 }
+
+/// [`SecurityConfig`] fields that change a [`crate::hash_secure`] digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigestParams {
+    pub rounds: u32,
+    pub security_level: u32,
+}
+
+impl DigestParams {
+    pub fn from_config(config: &SecurityConfig) -> Self {
+        Self {
+            rounds: config.rounds,
+            security_level: config.security_level,
+        }
+    }
+
+    /// The default [`SecurityConfig`] with these rounds and level.
+    pub fn config(&self) -> SecurityConfig {
+        SecurityConfig {
+            rounds: self.rounds,
+            security_level: self.security_level,
+            ..SecurityConfig::default()
+        }
+    }
+
+    fn check(&self) -> Result<(), &'static str> {
+        if !self.config().validate() {
+            return Err("Invalid digest parameters");
+        }
+        if self.rounds > MAX_ROUNDS {
+            return Err("Digest rounds exceed MAX_ROUNDS");
+        }
+        Ok(())
+    }
+}
+
+/// A digest tagged with the algorithm and parameters that produced it.
+///
+/// Equality compares digests in constant time.
+#[derive(Debug, Clone)]
+pub struct VersionedDigest {
+    algorithm: Algorithm,
+    params: Option<DigestParams>,
+    digest: Vec<u8>,
+}
+
+impl VersionedDigest {
+    /// Checks that `digest` has the output size of `algorithm` and that
+    /// `params`, if any, belong to a valid VortexHash-256 configuration with
+    /// at most [`MAX_ROUNDS`] rounds.
+    pub fn new(
+        algorithm: Algorithm,
+        params: Option<DigestParams>,
+        digest: Vec<u8>,
+    ) -> Result<Self, &'static str> {
+        if let Some(params) = params {
+            if algorithm != Algorithm::Vortex256 {
+                return Err("Parameters are only supported for vortex-256");
+            }
+            params.check()?;
+        }
+        if digest.len() != algorithm.output_size() {
+            return Err("Digest length does not match the algorithm");
+        }
+        Ok(Self {
+            algorithm,
+            params,
+            digest,
+        })
+    }
+
+    /// Digest of `data` under `algorithm`, without parameters.
+    pub fn compute(algorithm: Algorithm, data: &[u8]) -> Self {
+        Self {
+            algorithm,
+            params: None,
+            digest: algorithm.digest(data),
+        }
+    }
+
+    /// [`crate::hash_secure`] digest of `data`, recording the rounds and
+    /// security level of `config`, which may have at most [`MAX_ROUNDS`]
+    /// rounds.
+    pub fn compute_secure(data: &[u8], config: &SecurityConfig) -> Result<Self, &'static str> {
+        let params = DigestParams::from_config(config);
+        params.check()?;
+        Self::new(
+            Algorithm::Vortex256,
+            Some(params),
+            hash_secure(data, config)?.to_vec(),
+        )
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    pub fn params(&self) -> Option<DigestParams> {
+        self.params
+    }
+
+    /// [`ALGORITHM_VERSION`] for a VortexHash digest, `None` otherwise.
+    pub fn version(&self) -> Option<u32> {
+        is_vortex(self.algorithm).then_some(ALGORITHM_VERSION)
+    }
+
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    /// Recomputes the digest of `data` and compares it in constant time.
+    ///
+    /// The rounds are taken from the stored parameters; parsing rejects
+    /// more than [`MAX_ROUNDS`], which bounds the work per block.
+    pub fn verify(&self, data: &[u8]) -> bool {
+        let computed = match self.params {
+            Some(params) => match hash_secure(data, &params.config()) {
+                Ok(digest) => digest.to_vec(),
+                Err(_) => return false,
+            },
+            None => self.algorithm.digest(data),
+        };
+        computed.ct_eq(&self.digest).into()
+    }
+
+    /// Multihash encoding; see the module documentation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(self.digest.len() + 3 * MAX_VARINT_LEN);
+        if let Some(version) = self.version() {
+            write_varint(&mut payload, version.into());
+        }
+        let code = match self.params {
+            Some(params) => {
+                write_varint(&mut payload, params.rounds.into());
+                write_varint(&mut payload, params.security_level.into());
+                PARAMETERIZED_CODE
+            }
+            None => self.algorithm.multicodec(),
+        };
+        payload.extend_from_slice(&self.digest);
+
+        let mut out = Vec::with_capacity(payload.len() + 2 * MAX_VARINT_LEN);
+        write_varint(&mut out, code);
+        write_varint(&mut out, payload.len() as u64);
+        out.extend_from_slice(&payload);
+        out
+    }
+
+    /// Parses [`VersionedDigest::to_bytes`] output. Unknown codes and
+    /// versions, lengths that do not match the algorithm and trailing bytes
+    /// are rejected.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, &'static str> {
+        let code = read_varint(&mut bytes)?;
+        let length = read_varint(&mut bytes)?;
+        if length != bytes.len() as u64 {
+            return Err("Multihash length does not match its payload");
+        }
+        if code == PARAMETERIZED_CODE {
+            check_version(read_varint(&mut bytes)?)?;
+            let params = DigestParams {
+                rounds: read_u32_varint(&mut bytes)?,
+                security_level: read_u32_varint(&mut bytes)?,
+            };
+            return Self::new(Algorithm::Vortex256, Some(params), bytes.to_vec());
+        }
+        let algorithm = Algorithm::from_multicodec(code).ok_or("Unknown multihash code")?;
+        if is_vortex(algorithm) {
+            check_version(read_varint(&mut bytes)?)?;
+        }
+        Self::new(algorithm, None, bytes.to_vec())
+    }
+}
+
+impl PartialEq for VersionedDigest {
+    fn eq(&self, other: &Self) -> bool {
+        self.algorithm == other.algorithm
+            && self.params == other.params
+            && bool::from(self.digest.ct_eq(&other.digest))
+    }
+}
+
+impl Eq for VersionedDigest {}
+
+impl fmt::Display for VersionedDigest {
+    /// String encoding; see the module documentation.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "${}$", self.algorithm)?;
+        if let Some(version) = self.version() {
+            write!(f, "v={}$", version)?;
+        }
+        if let Some(params) = self.params {
+            write!(f, "r={},l={}$", params.rounds, params.security_level)?;
+        }
+        f.write_str(&to_hex(&self.digest))
+    }
+}
+
+impl FromStr for VersionedDigest {
+    type Err = &'static str;
+
+    /// Parses the string encoding. Only canonical algorithm names and
+    /// lowercase hex are accepted, so each digest has one representation.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        const MALFORMED: &str = "Malformed versioned digest";
+
+        let mut fields = text.split('$');
+        if fields.next() != Some("") {
+            return Err(MALFORMED);
+        }
+        let name = fields.next().ok_or(MALFORMED)?;
+        let algorithm: Algorithm = name.parse()?;
+        if algorithm.name() != name {
+            return Err("Non-canonical algorithm name");
+        }
+        if is_vortex(algorithm) {
+            check_version(parse_field(fields.next().ok_or(MALFORMED)?, "v")?.into())?;
+        }
+        let (params, digest) = match fields.collect::<Vec<_>>()[..] {
+            [digest] => (None, digest),
+            [params, digest] => (Some(params), digest),
+            _ => return Err(MALFORMED),
+        };
+        let params = match params {
+            Some(params) => {
+                let (rounds, level) = params.split_once(',').ok_or(MALFORMED)?;
+                Some(DigestParams {
+                    rounds: parse_field(rounds, "r")?,
+                    security_level: parse_field(level, "l")?,
+                })
+            }
+            None => None,
+        };
+        Self::new(algorithm, params, from_hex(digest)?)
+    }
+}
+
+fn is_vortex(algorithm: Algorithm) -> bool {
+    matches!(
+        algorithm,
+        Algorithm::Vortex224 | Algorithm::Vortex256 | Algorithm::Vortex384 | Algorithm::Vortex512
+    )
+}
+
+fn check_version(version: u64) -> Result<(), &'static str> {
+    if version == u64::from(ALGORITHM_VERSION) {
+        Ok(())
+    } else {
+        Err("Unsupported version")
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Reads a minimally encoded unsigned varint from the front of `bytes`.
+fn read_varint(bytes: &mut &[u8]) -> Result<u64, &'static str> {
+    let mut value = 0u64;
+    for (i, &byte) in bytes.iter().enumerate().take(MAX_VARINT_LEN) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            if byte == 0 && i > 0 {
+                return Err("Non-minimal varint");
+            }
+            *bytes = &bytes[i + 1..];
+            return Ok(value);
+        }
+    }
+    Err("Truncated or oversized varint")
+}
+
+fn read_u32_varint(bytes: &mut &[u8]) -> Result<u32, &'static str> {
+    u32::try_from(read_varint(bytes)?).map_err(|_| "Digest parameter out of range")
+}
@@ -0,0 +1,385 @@
+//! Memory-hard password hashing on the VortexHash permutation
+//!
+//! The construction follows Balloon hashing, with 128-byte blocks and an
+//! Argon2-style compression function built directly on the permutation:
+//!
+//! ```text
+//! G(X, Y) = P(X ^ Y) ^ X ^ Y
+//! ```
+//!
+//! Each of the `p` lanes owns `8 * m / p` blocks (`m` is in KiB) and is
+//! computed independently, in parallel:
+//!
+//! 1. **Expand**: the first two blocks are squeezed from a sponge over the
+//!    parameters, password, salt and lane number; then
+//!    `B[i] = G(B[i - 1], B[i - 2])`.
+//! 2. **Mix**, `t` times: for each block in order,
+//!    `B[i] = G(B[i], B[i - 1])` followed by [`DELTA`] steps
+//!    `B[i] = G(B[i], B[j])`, where each `j != i` is read from a sponge over
+//!    the salt, lane and pass. Indices do not depend on the password, so
+//!    memory access patterns leak nothing about it.
+//! 3. **Finalize**: the last block of every lane is absorbed, together with
+//!    the parameters and salt, into a sponge that outputs the hash.
+//!
+//! All sponges use the [`domain::PASSWORD`] byte. Results are encoded as
+//! PHC strings:
+//!
+//! ```text
+//! $vortex$v=1$m=<KiB>,t=<passes>,p=<lanes>$<salt>$<hash>
+//! ```
+//!
+//! with salt and hash in unpadded standard Base64.
+//!
+//! # Examples
+//!
+//! ```
+//! use vortex_hash::password::{self, Params};
+//!
+//! let params = Params::new(64, 1, 1).unwrap();
+//! let phc = password::hash_password(b"hunter2", &params).unwrap();
+//! assert!(password::verify(b"hunter2", &phc));
+//! assert!(!password::verify(b"hunter3", &phc));
+//! ```
+
+use crate::core::{domain, VortexHash, DEFAULT_CAPACITY};
+use crate::permutation::{Permutation, LANES};
+use crate::security::DEFAULT_ROUNDS;
+use crate::utilities::parse_field;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use rayon::prelude::*;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// Version of the construction, written as `v=` in PHC strings.
+pub const VERSION: u32 = 1;
+/// Length of the salt generated by [`hash_password`].
+pub const SALT_SIZE: usize = 16;
+/// Shortest salt accepted.
+pub const MIN_SALT_SIZE: usize = 8;
+/// Longest salt accepted.
+pub const MAX_SALT_SIZE: usize = 64;
+/// Length of the password hash.
+pub const OUTPUT_SIZE: usize = 32;
+/// Number of data-independent references mixed into each block per pass.
+pub const DELTA: usize = 3;
+
+/// Identifier used in PHC strings.
+const ALGORITHM_ID: &str = "vortex";
+/// Size of a memory block in bytes (one permutation state).
+const BLOCK_SIZE: usize = LANES * 8;
+// First byte absorbed by each sponge, separating the three uses.
+const TAG_INIT: u8 = 0x00;
+const TAG_INDEX: u8 = 0x01;
+const TAG_FINAL: u8 = 0x02;
+
+type Block = [u64; LANES];
+
+/// Cost parameters: memory in KiB, number of passes and number of lanes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl Params {
+    /// Memory cost used by [`Params::default`], in KiB.
+    pub const DEFAULT_M_COST: u32 = 1024;
+    /// Number of passes used by [`Params::default`].
+    pub const DEFAULT_T_COST: u32 = 3;
+    /// Number of lanes used by [`Params::default`].
+    pub const DEFAULT_P_COST: u32 = 1;
+
+    /// Validated parameters; see [`Params::validate`].
+    pub fn new(m_cost: u32, t_cost: u32, p_cost: u32) -> Result<Self, &'static str> {
+        let params = Self {
+            m_cost,
+            t_cost,
+            p_cost,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+
+    /// Checks that `t_cost` and `p_cost` are non-zero, `p_cost` is at most
+    /// 255 and every lane gets at least 8 KiB (`m_cost >= 8 * p_cost`).
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.t_cost == 0 {
+            return Err("Time cost must be at least 1");
+        }
+        if self.p_cost == 0 || self.p_cost > 255 {
+            return Err("Parallelism must be between 1 and 255");
+        }
+        if (self.m_cost as u64) < 8 * self.p_cost as u64 {
+            return Err("Memory cost must be at least 8 KiB per lane");
+        }
+        Ok(())
+    }
+
+    /// Blocks per lane.
+    fn lane_blocks(&self) -> usize {
+        (self.m_cost / self.p_cost) as usize * 1024 / BLOCK_SIZE
+    }
+
+    fn absorb_into(&self, sponge: &mut VortexHash) {
+        sponge.absorb(&VERSION.to_le_bytes());
+        sponge.absorb(&self.m_cost.to_le_bytes());
+        sponge.absorb(&self.t_cost.to_le_bytes());
+        sponge.absorb(&self.p_cost.to_le_bytes());
+    }
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            m_cost: Self::DEFAULT_M_COST,
+            t_cost: Self::DEFAULT_T_COST,
+            p_cost: Self::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// Hashes `password` with a fresh random salt and returns a PHC string.
+pub fn hash_password(password: &[u8], params: &Params) -> Result<String, &'static str> {
+    let mut salt = [0u8; SALT_SIZE];
+    OsRng.fill_bytes(&mut salt);
+    hash_password_with_salt(password, &salt, params)
+}
+
+/// Hashes `password` with the given salt and returns a PHC string.
+pub fn hash_password_with_salt(
+    password: &[u8],
+    salt: &[u8],
+    params: &Params,
+) -> Result<String, &'static str> {
+    let mut output = [0u8; OUTPUT_SIZE];
+    hash_raw(password, salt, params, &mut output)?;
+    let phc = format!(
+        "${}$v={}$m={},t={},p={}${}${}",
+        ALGORITHM_ID,
+        VERSION,
+        params.m_cost,
+        params.t_cost,
+        params.p_cost,
+        b64_encode(salt),
+        b64_encode(&output)
+    );
+    output.zeroize();
+    Ok(phc)
+}
+
+/// Computes the raw password hash.
+pub fn hash_raw(
+    password: &[u8],
+    salt: &[u8],
+    params: &Params,
+    output: &mut [u8; OUTPUT_SIZE],
+) -> Result<(), &'static str> {
+    params.validate()?;
+    if !(MIN_SALT_SIZE..=MAX_SALT_SIZE).contains(&salt.len()) {
+        return Err("Unsupported salt length");
+    }
+
+    let mut lanes: Vec<Block> = (0..params.p_cost)
+        .into_par_iter()
+        .map(|lane| fill_lane(password, salt, params, lane))
+        .collect();
+
+    let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+    sponge.absorb(&[TAG_FINAL]);
+    params.absorb_into(&mut sponge);
+    absorb_framed(&mut sponge, salt);
+    for block in &lanes {
+        sponge.absorb(&block_to_bytes(block));
+    }
+    sponge.finalize_into(domain::PASSWORD, output);
+    sponge.zeroize();
+    lanes.zeroize();
+    Ok(())
+}
+
+/// Checks `password` against a PHC string produced by [`hash_password`].
+///
+/// The hash comparison is constant-time. Malformed strings, unknown
+/// versions and invalid parameters are rejected. The cost parameters are
+/// taken from `phc`, so strings from untrusted sources should be checked
+/// with [`parse_params`] first.
+pub fn verify(password: &[u8], phc: &str) -> bool {
+    let Ok((params, salt, expected)) = parse(phc) else {
+        return false;
+    };
+    let mut output = [0u8; OUTPUT_SIZE];
+    if hash_raw(password, &salt, &params, &mut output).is_err() {
+        return false;
+    }
+    let equal = output[..].ct_eq(&expected).into();
+    output.zeroize();
+    equal
+}
+
+/// Returns the cost parameters recorded in a PHC string.
+pub fn parse_params(phc: &str) -> Result<Params, &'static str> {
+    parse(phc).map(|(params, _, _)| params)
+}
+
+fn parse(phc: &str) -> Result<(Params, Vec<u8>, Vec<u8>), &'static str> {
+    const MALFORMED: &str = "Malformed PHC string";
+
+    let mut fields = phc.split('$');
+    if fields.next() != Some("") || fields.next() != Some(ALGORITHM_ID) {
+        return Err("Unsupported algorithm identifier");
+    }
+    if parse_field(fields.next().ok_or(MALFORMED)?, "v")? != VERSION {
+        return Err("Unsupported version");
+    }
+
+    let mut costs = fields.next().ok_or(MALFORMED)?.split(',');
+    let mut cost = |name| parse_field(costs.next().ok_or(MALFORMED)?, name);
+    let params = Params::new(cost("m")?, cost("t")?, cost("p")?)?;
+    if costs.next().is_some() {
+        return Err(MALFORMED);
+    }
+
+    let salt = b64_decode(fields.next().ok_or(MALFORMED)?)?;
+    let hash = b64_decode(fields.next().ok_or(MALFORMED)?)?;
+    if fields.next().is_some() {
+        return Err(MALFORMED);
+    }
+    if !(MIN_SALT_SIZE..=MAX_SALT_SIZE).contains(&salt.len()) {
+        return Err("Unsupported salt length");
+    }
+    if hash.len() != OUTPUT_SIZE {
+        return Err("Unsupported hash length");
+    }
+    Ok((params, salt, hash))
+}
+
+/// Expands and mixes one lane, returning its last block.
+fn fill_lane(password: &[u8], salt: &[u8], params: &Params, lane: u32) -> Block {
+    let n = params.lane_blocks();
+    let permutation = Permutation::new(DEFAULT_ROUNDS);
+    let mut blocks = vec![[0u64; LANES]; n];
+
+    let mut seed = [0u8; 2 * BLOCK_SIZE];
+    let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+    sponge.absorb(&[TAG_INIT]);
+    params.absorb_into(&mut sponge);
+    absorb_framed(&mut sponge, password);
+    absorb_framed(&mut sponge, salt);
+    sponge.absorb(&lane.to_le_bytes());
+    sponge.finalize_into(domain::PASSWORD, &mut seed);
+    sponge.zeroize();
+    blocks[0] = block_from_bytes(&seed[..BLOCK_SIZE]);
+    blocks[1] = block_from_bytes(&seed[BLOCK_SIZE..]);
+    seed.zeroize();
+
+    for i in 2..n {
+        blocks[i] = compress(&permutation, &blocks[i - 1], &blocks[i - 2]);
+    }
+
+    let mut indices = vec![0u8; n * DELTA * 8];
+    for pass in 0..params.t_cost {
+        let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+        sponge.absorb(&[TAG_INDEX]);
+        params.absorb_into(&mut sponge);
+        absorb_framed(&mut sponge, salt);
+        sponge.absorb(&lane.to_le_bytes());
+        sponge.absorb(&pass.to_le_bytes());
+        sponge.finalize_into(domain::PASSWORD, &mut indices);
+
+        let mut indices = indices.chunks_exact(8);
+        for i in 0..n {
+            blocks[i] = compress(&permutation, &blocks[i], &blocks[(i + n - 1) % n]);
+            for _ in 0..DELTA {
+                let bytes = indices.next().expect("index stream covers the pass");
+                let r = u64::from_le_bytes(bytes.try_into().unwrap());
+                // Uniform over every block except `i`.
+                let mut j = (r % (n as u64 - 1)) as usize;
+                if j >= i {
+                    j += 1;
+                }
+                blocks[i] = compress(&permutation, &blocks[i], &blocks[j]);
+            }
+        }
+    }
+
+    let last = blocks[n - 1];
+    blocks.zeroize();
+    last
+}
+
+/// `G(x, y) = P(x ^ y) ^ x ^ y`.
+fn compress(permutation: &Permutation, x: &Block, y: &Block) -> Block {
+    let mut r = [0u64; LANES];
+    for k in 0..LANES {
+        r[k] = x[k] ^ y[k];
+    }
+    let mut z = r;
+    permutation.permute(&mut z);
+    for k in 0..LANES {
+        z[k] ^= r[k];
+    }
+    z
+}
+
+/// Absorbs the little-endian 32-bit length of `data` followed by `data`.
+fn absorb_framed(sponge: &mut VortexHash, data: &[u8]) {
+    sponge.absorb(&(data.len() as u32).to_le_bytes());
+    sponge.absorb(data);
+}
+
+fn block_from_bytes(bytes: &[u8]) -> Block {
+    let mut block = [0u64; LANES];
+    for (lane, chunk) in block.iter_mut().zip(bytes.chunks_exact(8)) {
+        *lane = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+    block
+}
+
+fn block_to_bytes(block: &Block) -> [u8; BLOCK_SIZE] {
+    let mut bytes = [0u8; BLOCK_SIZE];
+    for (lane, chunk) in block.iter().zip(bytes.chunks_exact_mut(8)) {
+        chunk.copy_from_slice(&lane.to_le_bytes());
+    }
+    bytes
+}
+
+const B64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard Base64 without padding, as used by PHC strings.
+fn b64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let mut buf = [0u8; 3];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let n = u32::from_be_bytes([0, buf[0], buf[1], buf[2]]);
+        for k in 0..=chunk.len() {
+            out.push(B64_ALPHABET[(n >> (18 - 6 * k)) as usize & 63] as char);
+        }
+    }
+    out
+}
+
+fn b64_decode(text: &str) -> Result<Vec<u8>, &'static str> {
+    const INVALID: &str = "Invalid Base64";
+    if text.len() % 4 == 1 {
+        return Err(INVALID);
+    }
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    for chunk in text.as_bytes().chunks(4) {
+        let mut n = 0u32;
+        for (k, &c) in chunk.iter().enumerate() {
+            let value = B64_ALPHABET.iter().position(|&a| a == c).ok_or(INVALID)?;
+            n |= (value as u32) << (18 - 6 * k);
+        }
+        let bytes = n.to_be_bytes();
+        let len = chunk.len() - 1;
+        // Reject non-canonical encodings with stray low bits.
+        if bytes[1 + len..].iter().any(|&b| b != 0) {
+            return Err(INVALID);
+        }
+        out.extend_from_slice(&bytes[1..1 + len]);
+    }
+    Ok(out)
+}
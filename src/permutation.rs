@@ -0,0 +1,270 @@
+//! The VortexHash permutation
+//!
+//! The 1024-bit state is handled as sixteen little-endian `u64` lanes,
+//! arranged as a 4x4 grid (`lane = 4 * row + column`). Each round applies:
+//!
+//! 1. **ARX step**: for each lane `i` in order,
+//!    `a[i] = rotl(a[i] + a[i + 1], ROTATIONS[i]) ^ a[i + 7]` (indices mod 16).
+//! 2. **Round constant**: `a[0] ^= RC[round % 255]`.
+//! 3. **S-box layer**: S-box `SBOXES[round % 8]` is applied bitsliced to every
+//!    row; bit `k` of the four lanes of a row forms one 4-bit input.
+//! 4. **Linear mixing**: for each column, with `t` the XOR of its four lanes,
+//!    `a[4 * row + col] ^= rotl(t, MIX_ROTATIONS[row])`.
+//!
+//! Every step is invertible, so the whole round is a permutation of the
+//! state. [`Permutation::permute`] is the unrolled implementation used by
+//! the sponge; [`Permutation::permute_reference`] follows the description
+//! above literally, using table lookups, and exists to cross-check it.
+
+/// Number of 64-bit lanes in the state.
+pub const LANES: usize = 16;
+
+/// Round constants `RC[i]`, generated by the Keccak LFSR (`x^8 + x^6 + x^5 +
+/// x^4 + 1`). The sequence repeats after 255 rounds.
+pub const RC: [u64; 255] = round_constants();
+
+/// Rotation amounts of the ARX step, one per lane.
+pub const ROTATIONS: [u32; LANES] = [13, 29, 7, 41, 19, 53, 3, 37, 23, 47, 11, 59, 31, 5, 43, 17];
+
+/// Rotation amounts of the mixing layer, one per row.
+///
+/// The four amounts are distinct and non-zero, which keeps the layer
+/// invertible: its column map is multiplication by `1 + x^9 + x^22 + x^37 +
+/// x^51`, an odd-weight polynomial and therefore a unit modulo `x^64 + 1`.
+pub const MIX_ROTATIONS: [u32; 4] = [9, 22, 37, 51];
+
+/// The eight 4-bit S-boxes (the Serpent S-boxes S0..S7).
+pub const SBOXES: [[u8; 16]; 8] = [
+    [3, 8, 15, 1, 10, 6, 5, 11, 14, 13, 4, 2, 7, 0, 9, 12],
+    [15, 12, 2, 7, 9, 0, 5, 10, 1, 11, 14, 8, 6, 13, 3, 4],
+    [8, 6, 7, 9, 3, 12, 10, 15, 13, 1, 14, 4, 0, 11, 5, 2],
+    [0, 15, 11, 8, 12, 9, 6, 3, 13, 1, 2, 4, 10, 7, 5, 14],
+    [1, 15, 8, 3, 12, 0, 11, 6, 2, 5, 4, 10, 9, 14, 7, 13],
+    [15, 5, 2, 11, 4, 10, 9, 12, 0, 3, 14, 8, 13, 6, 7, 1],
+    [7, 2, 12, 5, 8, 4, 6, 11, 14, 9, 1, 15, 13, 3, 10, 0],
+    [1, 13, 15, 0, 14, 8, 2, 11, 7, 4, 12, 10, 9, 3, 5, 6],
+];
+
+/// Algebraic normal form of each S-box output bit: bit `u` of
+/// `SBOX_ANF[s][j]` is set when the monomial `prod(x_b for b in u)` appears
+/// in output bit `j` of S-box `s`. Used by the bitsliced S-box layer.
+const SBOX_ANF: [[u16; 4]; 8] = sbox_anf();
+
+const fn round_constants() -> [u64; 255] {
+    let mut rc = [0u64; 255];
+    let mut round = 0;
+    while round < 255 {
+        let mut j = 0;
+        while j < 7 {
+            rc[round] |= lfsr_bit(j + 7 * round) << ((1 << j) - 1);
+            j += 1;
+        }
+        round += 1;
+    }
+    rc
+}
+
+const fn lfsr_bit(t: usize) -> u64 {
+    let mut r: u16 = 1;
+    let mut i = 0;
+    while i < t % 255 {
+        r <<= 1;
+        if r & 0x100 != 0 {
+            r ^= 0x171;
+        }
+        i += 1;
+    }
+    (r & 1) as u64
+}
+
+const fn sbox_anf() -> [[u16; 4]; 8] {
+    let mut anf = [[0u16; 4]; 8];
+    let mut s = 0;
+    while s < 8 {
+        let mut j = 0;
+        while j < 4 {
+            // Truth table of output bit j, then the Moebius transform.
+            let mut coeffs = [0u8; 16];
+            let mut x = 0;
+            while x < 16 {
+                coeffs[x] = (SBOXES[s][x] >> j) & 1;
+                x += 1;
+            }
+            let mut bit = 0;
+            while bit < 4 {
+                let mut u = 0;
+                while u < 16 {
+                    if u & (1 << bit) != 0 {
+                        coeffs[u] ^= coeffs[u ^ (1 << bit)];
+                    }
+                    u += 1;
+                }
+                bit += 1;
+            }
+            let mut u = 0;
+            while u < 16 {
+                anf[s][j] |= (coeffs[u] as u16) << u;
+                u += 1;
+            }
+            j += 1;
+        }
+        s += 1;
+    }
+    anf
+}
+
+/// The VortexHash permutation with a fixed round count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permutation {
+    rounds: u32,
+}
+
+impl Permutation {
+    pub const fn new(rounds: u32) -> Self {
+        Self { rounds }
+    }
+
+    pub const fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    /// Applies the permutation to a byte state, read as little-endian lanes.
+    pub fn permute_bytes(&self, state: &mut [u8; LANES * 8]) {
+        let mut lanes = [0u64; LANES];
+        for (lane, bytes) in lanes.iter_mut().zip(state.chunks_exact(8)) {
+            *lane = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        self.permute(&mut lanes);
+        for (lane, bytes) in lanes.iter().zip(state.chunks_exact_mut(8)) {
+            bytes.copy_from_slice(&lane.to_le_bytes());
+        }
+    }
+
+    /// Unrolled implementation of the permutation.
+    pub fn permute(&self, a: &mut [u64; LANES]) {
+        for round in 0..self.rounds as usize {
+            macro_rules! arx {
+                ($($i:literal),*) => {$(
+                    a[$i] = a[$i].wrapping_add(a[($i + 1) % LANES]).rotate_left(ROTATIONS[$i])
+                        ^ a[($i + 7) % LANES];
+                )*};
+            }
+            arx!(0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+
+            a[0] ^= RC[round % RC.len()];
+
+            match round % 8 {
+                0 => sbox_layer::<0>(a),
+                1 => sbox_layer::<1>(a),
+                2 => sbox_layer::<2>(a),
+                3 => sbox_layer::<3>(a),
+                4 => sbox_layer::<4>(a),
+                5 => sbox_layer::<5>(a),
+                6 => sbox_layer::<6>(a),
+                _ => sbox_layer::<7>(a),
+            }
+
+            macro_rules! mix {
+                ($($col:literal),*) => {$(
+                    let t = a[$col] ^ a[4 + $col] ^ a[8 + $col] ^ a[12 + $col];
+                    a[$col] ^= t.rotate_left(MIX_ROTATIONS[0]);
+                    a[4 + $col] ^= t.rotate_left(MIX_ROTATIONS[1]);
+                    a[8 + $col] ^= t.rotate_left(MIX_ROTATIONS[2]);
+                    a[12 + $col] ^= t.rotate_left(MIX_ROTATIONS[3]);
+                )*};
+            }
+            mix!(0, 1, 2, 3);
+        }
+    }
+
+    /// Straightforward reference implementation of the permutation.
+    ///
+    /// The S-box layer uses table lookups indexed by state bits, so this
+    /// version is not constant-time and must not be used on secret data.
+    pub fn permute_reference(&self, a: &mut [u64; LANES]) {
+        for round in 0..self.rounds as usize {
+            // ARX step
+            for i in 0..LANES {
+                let sum = a[i].wrapping_add(a[(i + 1) % LANES]);
+                a[i] = sum.rotate_left(ROTATIONS[i]) ^ a[(i + 7) % LANES];
+            }
+
+            // Round constant
+            a[0] ^= RC[round % RC.len()];
+
+            // S-box layer
+            let sbox = &SBOXES[round % 8];
+            for row in 0..4 {
+                let mut out = [0u64; 4];
+                for bit in 0..64 {
+                    let mut input = 0u8;
+                    for plane in 0..4 {
+                        input |= (((a[4 * row + plane] >> bit) & 1) as u8) << plane;
+                    }
+                    let output = sbox[input as usize];
+                    for (plane, lane) in out.iter_mut().enumerate() {
+                        *lane |= (((output >> plane) & 1) as u64) << bit;
+                    }
+                }
+                a[4 * row..4 * row + 4].copy_from_slice(&out);
+            }
+
+            // Linear mixing layer
+            for col in 0..4 {
+                let mut t = 0u64;
+                for row in 0..4 {
+                    t ^= a[4 * row + col];
+                }
+                for row in 0..4 {
+                    a[4 * row + col] ^= t.rotate_left(MIX_ROTATIONS[row]);
+                }
+            }
+        }
+    }
+}
+
+/// Bitsliced S-box layer for S-box `S`, evaluated from its algebraic normal
+/// form so that no state-dependent memory access takes place.
+#[inline(always)]
+fn sbox_layer<const S: usize>(a: &mut [u64; LANES]) {
+    for row in 0..4 {
+        let x0 = a[4 * row];
+        let x1 = a[4 * row + 1];
+        let x2 = a[4 * row + 2];
+        let x3 = a[4 * row + 3];
+        let x01 = x0 & x1;
+        let x02 = x0 & x2;
+        let x12 = x1 & x2;
+        let x012 = x01 & x2;
+        let monomials = [
+            !0,
+            x0,
+            x1,
+            x01,
+            x2,
+            x02,
+            x12,
+            x012,
+            x3,
+            x0 & x3,
+            x1 & x3,
+            x01 & x3,
+            x2 & x3,
+            x02 & x3,
+            x12 & x3,
+            x012 & x3,
+        ];
+        for j in 0..4 {
+            // The ANF is a compile-time constant for each `S`, so these
+            // branches are resolved when the function is monomorphized.
+            let anf = SBOX_ANF[S][j];
+            let mut y = 0u64;
+            for (u, monomial) in monomials.iter().enumerate() {
+                if (anf >> u) & 1 == 1 {
+                    y ^= monomial;
+                }
+            }
+            a[4 * row + j] = y;
+        }
+    }
+}
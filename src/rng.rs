@@ -0,0 +1,169 @@
+//! Deterministic random bit generator on the VortexHash sponge
+//!
+//! `VortexRng` keeps a 32-byte secret key `K` and produces output with
+//! fast key erasure:
+//!
+//! - **Seed**: `K = H_seed(0^32 || seed)`, where `H_seed` is the sponge
+//!   (64-byte capacity) padded with [`domain::RNG_SEED`].
+//! - **Reseed**: `K = H_seed(K || entropy)`; buffered output is discarded.
+//! - **Generate**: the sponge absorbs `K` and squeezes [`BLOCK_SIZE`] bytes
+//!   padded with [`domain::RNG_OUTPUT`]. The first 32 bytes replace `K`
+//!   and the rest are handed out in order, each wiped as it is returned.
+//!
+//! The key is ratcheted before any of a block's output is released, so a
+//! compromise of the generator state reveals nothing about earlier output.
+//! The output stream only depends on the seed and reseed inputs, not on
+//! how it is split across calls.
+//!
+//! # Examples
+//!
+//! ```
+//! use rand::{Rng, SeedableRng};
+//! use vortex_hash::rng::VortexRng;
+//!
+//! let mut a = VortexRng::from_seed([7u8; 32]);
+//! let mut b = VortexRng::from_seed([7u8; 32]);
+//! assert_eq!(a.gen::<u64>(), b.gen::<u64>());
+//!
+//! let mut key = [0u8; 32];
+//! VortexRng::new().fill(&mut key);
+//! ```
+
+use crate::core::{domain, VortexHash, DEFAULT_CAPACITY};
+use crate::security::DEFAULT_ROUNDS;
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore, SeedableRng};
+use zeroize::Zeroize;
+
+/// Size of the seed and of the internal key.
+pub const SEED_SIZE: usize = 32;
+
+/// Bytes squeezed per generate call, including the next key.
+pub const BLOCK_SIZE: usize = 256;
+
+/// Output bytes released per generate call.
+const BUFFER_SIZE: usize = BLOCK_SIZE - SEED_SIZE;
+
+/// Sponge-based CSPRNG with reseeding and forward secrecy; wiped when
+/// dropped.
+pub struct VortexRng {
+    key: [u8; SEED_SIZE],
+    buffer: [u8; BUFFER_SIZE],
+    pos: usize, // Next unread byte of `buffer`
+}
+
+impl VortexRng {
+    /// Creates a generator seeded from the operating system's entropy
+    /// source.
+    pub fn new() -> Self {
+        let mut seed = [0u8; SEED_SIZE];
+        OsRng.fill_bytes(&mut seed);
+        let rng = Self::from_seed(seed);
+        seed.zeroize();
+        rng
+    }
+
+    /// Mixes `entropy` into the key and discards buffered output.
+    ///
+    /// Reseeding with the same input from the same state is deterministic,
+    /// so simulations stay reproducible.
+    pub fn reseed(&mut self, entropy: &[u8]) {
+        let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+        sponge.absorb(&self.key);
+        sponge.absorb(entropy);
+        sponge.finalize_into(domain::RNG_SEED, &mut self.key);
+        sponge.zeroize();
+        self.buffer.zeroize();
+        self.pos = BUFFER_SIZE;
+    }
+
+    /// Reseeds with [`SEED_SIZE`] bytes from the operating system's entropy
+    /// source.
+    pub fn reseed_from_os(&mut self) {
+        let mut entropy = [0u8; SEED_SIZE];
+        OsRng.fill_bytes(&mut entropy);
+        self.reseed(&entropy);
+        entropy.zeroize();
+    }
+
+    /// Replaces the key and refills the buffer from one generate call.
+    fn refill(&mut self) {
+        let mut block = [0u8; BLOCK_SIZE];
+        let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+        sponge.absorb(&self.key);
+        sponge.finalize_into(domain::RNG_OUTPUT, &mut block);
+        sponge.zeroize();
+        self.key.copy_from_slice(&block[..SEED_SIZE]);
+        self.buffer.copy_from_slice(&block[SEED_SIZE..]);
+        block.zeroize();
+        self.pos = 0;
+    }
+}
+
+impl Default for VortexRng {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SeedableRng for VortexRng {
+    type Seed = [u8; SEED_SIZE];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut rng = Self {
+            key: [0u8; SEED_SIZE],
+            buffer: [0u8; BUFFER_SIZE],
+            pos: BUFFER_SIZE,
+        };
+        rng.reseed(&seed);
+        rng
+    }
+}
+
+impl RngCore for VortexRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, mut dest: &mut [u8]) {
+        while !dest.is_empty() {
+            if self.pos == BUFFER_SIZE {
+                self.refill();
+            }
+            let take = (BUFFER_SIZE - self.pos).min(dest.len());
+            let output = &mut self.buffer[self.pos..self.pos + take];
+            dest[..take].copy_from_slice(output);
+            output.zeroize();
+            self.pos += take;
+            dest = &mut dest[take..];
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for VortexRng {}
+
+impl Drop for VortexRng {
+    fn drop(&mut self) {
+        self.key.zeroize();
+        self.buffer.zeroize();
+    }
+}
+
+impl std::fmt::Debug for VortexRng {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("VortexRng(..)")
+    }
+}
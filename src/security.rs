@@ -1,6 +1,10 @@
 use std::fmt;
 
-#[derive(Debug, Clone, Default)]
+/// Permutation rounds used by [`SecurityConfig::new`] and by the modes with
+/// fixed parameters.
+pub const DEFAULT_ROUNDS: u32 = 64;
+
+#[derive(Debug, Clone)]
 pub struct SecurityConfig {
     pub rounds: u32,
     pub security_level: u32,
@@ -12,16 +16,41 @@ impl SecurityConfig {
     #[inline(always)]
     pub fn new() -> Self {
         Self {
-            rounds: 64,
+            rounds: DEFAULT_ROUNDS,
             security_level: 256,
             constant_time: true,
             side_channel_protection: true,
         }
     }
 
+    /// Returns `true` if the configuration can be used to build a hasher.
+    ///
+    /// `security_level` must be one of 128, 256 or 512 bits, since each level
+    /// maps to a fixed sponge capacity (see [`SecurityConfig::capacity`]).
     #[inline(always)]
     pub fn validate(&self) -> bool {
-        self.rounds > 0 && self.security_level >= 128 && self.constant_time
+        self.rounds > 0 && self.capacity().is_some() && self.constant_time
+    }
+
+    /// Sponge capacity in bytes selected by `security_level`.
+    ///
+    /// The capacity is twice the security level, capped so that at least
+    /// 16 bytes of the 128-byte state remain as rate. Returns `None` for
+    /// unsupported levels.
+    #[inline(always)]
+    pub fn capacity(&self) -> Option<usize> {
+        match self.security_level {
+            128 => Some(32),
+            256 => Some(64),
+            512 => Some(112),
+            _ => None,
+        }
+    }
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
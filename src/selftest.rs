@@ -0,0 +1,192 @@
+//! Power-on self-tests behind [`crate::health_check`]
+//!
+//! Each check runs the module's public entry points on fixed inputs and
+//! compares the result with a published vector from [`crate::kat`] or, for
+//! the SHA-256 backends, with the FIPS 180-4 digest of `"abc"`. A check
+//! returns the reason for the first mismatch it finds.
+
+use crate::core::VortexHash;
+use crate::kat::{
+    HASH_VECTORS, MAC_VECTORS, PERMUTATION_ZERO_STATE, SECURE_HASH_VECTORS, XOF_VECTORS,
+};
+use crate::utilities::to_hex;
+use crate::{Permutation, SecurityConfig, VortexMac};
+use std::time::{Duration, Instant};
+
+/// SHA-256 of `"abc"` (FIPS 180-4, appendix B.1).
+const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+/// Input size and minimum sampling time of [`measure_throughput`].
+const THROUGHPUT_INPUT_SIZE: usize = 64 * 1024;
+const THROUGHPUT_MIN_TIME: Duration = Duration::from_millis(5);
+
+fn expect(output: &[u8], expected: &str, reason: &'static str) -> Result<(), &'static str> {
+    if to_hex(output) == expected {
+        Ok(())
+    } else {
+        Err(reason)
+    }
+}
+
+/// Permutation, `hash`, `hash_secure`, `hash_constant_time`, the MAC and
+/// the XOF.
+pub fn check_core() -> Result<(), &'static str> {
+    let mut state = [0u8; 128];
+    Permutation::new(64).permute_bytes(&mut state);
+    expect(&state, PERMUTATION_ZERO_STATE, "permutation KAT mismatch")?;
+
+    for vector in HASH_VECTORS {
+        expect(
+            &crate::hash(vector.input),
+            vector.digest,
+            "hash KAT mismatch",
+        )?;
+        expect(
+            &crate::hash_constant_time(vector.input),
+            vector.digest,
+            "hash_constant_time KAT mismatch",
+        )?;
+    }
+    for vector in SECURE_HASH_VECTORS {
+        let config = SecurityConfig {
+            rounds: vector.rounds,
+            security_level: vector.security_level,
+            ..SecurityConfig::default()
+        };
+        expect(
+            &crate::hash_secure(vector.input, &config)?,
+            vector.digest,
+            "hash_secure KAT mismatch",
+        )?;
+    }
+    for vector in MAC_VECTORS {
+        let mut mac = VortexMac::with_customization(vector.key, vector.customization);
+        mac.update(vector.input);
+        expect(&mac.finalize(), vector.tag, "MAC KAT mismatch")?;
+    }
+    for vector in XOF_VECTORS {
+        let mut output = vec![0u8; vector.output.len() / 2];
+        crate::hash_to(vector.input, &mut output);
+        expect(&output, vector.output, "XOF KAT mismatch")?;
+    }
+    Ok(())
+}
+
+/// The default configuration validates and an invalid one is rejected.
+pub fn check_security() -> Result<(), &'static str> {
+    if !SecurityConfig::default().validate() {
+        return Err("default SecurityConfig rejected");
+    }
+    let invalid = SecurityConfig {
+        rounds: 0,
+        ..SecurityConfig::default()
+    };
+    if invalid.validate() || VortexHash::new(&invalid).is_ok() {
+        return Err("zero-round SecurityConfig accepted");
+    }
+    Ok(())
+}
+
+/// The accelerated entry point matches `hash` and the backends initialize.
+#[allow(deprecated)]
+pub fn check_hardware() -> Result<(), &'static str> {
+    for vector in HASH_VECTORS {
+        expect(
+            &crate::hash_ultra_optimized(vector.input),
+            vector.digest,
+            "hash_ultra_optimized KAT mismatch",
+        )?;
+    }
+    crate::hardware::cuda::init_cuda()?;
+    crate::hardware::vulkan::init_vulkan()?;
+    Ok(())
+}
+
+/// [`crate::ct_eq`] agrees with `subtle` and with `==`.
+pub fn check_utilities() -> Result<(), &'static str> {
+    let a = crate::hash(b"a");
+    let b = crate::hash(b"b");
+    let cases: [(&[u8], &[u8]); 5] = [
+        (&a, &a),
+        (&a, &b),
+        (&a, &a[..31]),
+        (&a[..1], &b[..1]),
+        (&[], &[]),
+    ];
+    for (x, y) in cases {
+        let expected = x == y;
+        let subtle = bool::from(subtle::ConstantTimeEq::ct_eq(x, y));
+        if subtle != expected || crate::ct_eq(x, y) != expected {
+            return Err("ct_eq disagrees with subtle");
+        }
+    }
+    expect(
+        &crate::utils_hash(b"abc"),
+        SHA256_ABC,
+        "utils_hash KAT mismatch",
+    )
+}
+
+/// The RustCrypto `Digest` implementation matches `hash`.
+pub fn check_ecosystem() -> Result<(), &'static str> {
+    for vector in HASH_VECTORS {
+        let output = <VortexHash as digest::Digest>::digest(vector.input);
+        expect(&output, vector.digest, "Digest trait KAT mismatch")?;
+    }
+    Ok(())
+}
+
+/// `UniversalHash::hash_compatible` is SHA-256, and the registry's SHA-256
+/// and VortexHash-256 entries match their vectors.
+pub fn check_compatibility() -> Result<(), &'static str> {
+    use crate::compatibility::Algorithm;
+    expect(
+        &crate::UniversalHash::hash_compatible(b"abc"),
+        SHA256_ABC,
+        "compatibility backend KAT mismatch",
+    )?;
+    expect(
+        &Algorithm::Sha256.digest(b"abc"),
+        SHA256_ABC,
+        "compatibility registry KAT mismatch",
+    )?;
+    for vector in HASH_VECTORS {
+        expect(
+            &Algorithm::Vortex256.digest(vector.input),
+            vector.digest,
+            "compatibility registry KAT mismatch",
+        )?;
+    }
+    Ok(())
+}
+
+/// `MigrationHelper::migrate_from_legacy` is SHA-256.
+pub fn check_migration() -> Result<(), &'static str> {
+    expect(
+        &crate::MigrationHelper::migrate_from_legacy(b"abc"),
+        SHA256_ABC,
+        "migration backend KAT mismatch",
+    )
+}
+
+/// `FallbackHash::hash_fallback` is SHA-256.
+pub fn check_fallback() -> Result<(), &'static str> {
+    expect(
+        &crate::FallbackHash::hash_fallback(b"abc"),
+        SHA256_ABC,
+        "fallback backend KAT mismatch",
+    )
+}
+
+/// Throughput of [`crate::hash`] in MiB/s, hashing 64 KiB repeatedly for
+/// at least 5 ms.
+pub fn measure_throughput() -> f64 {
+    let input = vec![0x5au8; THROUGHPUT_INPUT_SIZE];
+    let start = Instant::now();
+    let mut bytes = 0usize;
+    while bytes == 0 || start.elapsed() < THROUGHPUT_MIN_TIME {
+        std::hint::black_box(crate::hash(std::hint::black_box(&input)));
+        bytes += input.len();
+    }
+    bytes as f64 / (1024.0 * 1024.0) / start.elapsed().as_secs_f64()
+}
@@ -0,0 +1,549 @@
+//! Stateless hash-based signatures (SPHINCS+-style hypertree)
+//!
+//! Unlike [`crate::xmss`], a signing key carries no state: the leaf used
+//! for each signature is selected by the message digest itself, so
+//! restoring an old copy of a key, or signing from several instances at
+//! once, is safe.
+//!
+//! A signature consists of a message randomizer `R`, a FORS few-time
+//! signature of the digest, and a hypertree of `d` layers of Merkle trees
+//! over [`crate::wots`] keys, each layer signing the root of the layer
+//! below it. All hashing goes through the tweakable hash of
+//! [`crate::thash`]: separate domain bytes for the tweakable hash, the
+//! secret-value PRF, the message randomizer and the message digest, and an
+//! address binding each call to its layer, tree, key pair and position.
+//!
+//! # Parameter sets
+//!
+//! Both sets use `n = 32` and `w = 16` and the tree shapes of
+//! SPHINCS+-256s and SPHINCS+-256f:
+//!
+//! | Set | `h` | `d` | FORS `a` | FORS `k` | Signature bytes |
+//! |-----|-----|-----|----------|----------|-----------------|
+//! | [`ParameterSet::Small`] | 64 | 8 | 14 | 22 | 29 792 |
+//! | [`ParameterSet::Fast`] | 68 | 17 | 9 | 35 | 49 856 |
+//!
+//! "Small" has shorter signatures; "fast" signs several times faster.
+//!
+//! # Wire format
+//!
+//! ```text
+//! VerifyingKey: set id (1) || pk_seed (32) || root (32)
+//! SigningKey:   set id (1) || sk_seed (32) || sk_prf (32)
+//!               || pk_seed (32) || root (32)
+//! Signature:    R (32)
+//!               || k * (FORS secret (32) || FORS auth path (a * 32))
+//!               || d * (WOTS signature (67 * 32) || auth path (h/d * 32))
+//! ```
+//!
+//! The set id is 1 for "small" and 2 for "fast". Signatures carry no
+//! header; their length is fixed by the parameter set of the key.
+//!
+//! The digest `H_msg(R, pk_seed, root, message)` is split into the FORS
+//! message (`k` big-endian `a`-bit indices), the hypertree index (the next
+//! `h - h/d` bits) and the leaf index (the next `h/d` bits), each field
+//! starting on a byte boundary.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use vortex_hash::sphincs::{ParameterSet, SigningKey};
+//!
+//! let signing_key = SigningKey::generate(ParameterSet::Fast);
+//! let verifying_key = signing_key.verifying_key();
+//! let signature = signing_key.sign(b"message");
+//! assert!(verifying_key.verify(b"message", &signature));
+//! ```
+
+use crate::thash::{
+    auth_path, build_levels, hash_message, prf_message, root_from_auth_path, Address,
+    TweakableHash, FORS_ROOTS, FORS_TREE, N, TREE,
+};
+use crate::wots;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use rayon::prelude::*;
+use zeroize::Zeroize;
+
+/// Size of a serialized [`VerifyingKey`].
+pub const VERIFYING_KEY_SIZE: usize = 1 + 2 * N;
+/// Size of a serialized [`SigningKey`].
+pub const SIGNING_KEY_SIZE: usize = 1 + 4 * N;
+
+/// Parameter set of a key pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterSet {
+    /// Shorter signatures, slower signing.
+    Small,
+    /// Faster signing, longer signatures.
+    Fast,
+}
+
+/// Tree shape of a parameter set.
+struct Params {
+    /// Total hypertree height.
+    h: u32,
+    /// Number of hypertree layers.
+    d: u32,
+    /// Height of each FORS tree.
+    a: u32,
+    /// Number of FORS trees.
+    k: u32,
+}
+
+impl Params {
+    /// Height of each hypertree layer.
+    const fn hp(&self) -> u32 {
+        self.h / self.d
+    }
+
+    const fn fors_message_bytes(&self) -> usize {
+        (self.k * self.a).div_ceil(8) as usize
+    }
+
+    const fn tree_bytes(&self) -> usize {
+        (self.h - self.hp()).div_ceil(8) as usize
+    }
+
+    const fn leaf_bytes(&self) -> usize {
+        self.hp().div_ceil(8) as usize
+    }
+
+    const fn signature_size(&self) -> usize {
+        N * (1 + (self.k * (self.a + 1)) as usize + self.h as usize + self.d as usize * wots::LEN)
+    }
+}
+
+impl ParameterSet {
+    const fn params(self) -> Params {
+        match self {
+            ParameterSet::Small => Params {
+                h: 64,
+                d: 8,
+                a: 14,
+                k: 22,
+            },
+            ParameterSet::Fast => Params {
+                h: 68,
+                d: 17,
+                a: 9,
+                k: 35,
+            },
+        }
+    }
+
+    /// Identifier used in serialized keys.
+    pub const fn id(self) -> u8 {
+        match self {
+            ParameterSet::Small => 1,
+            ParameterSet::Fast => 2,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Result<Self, &'static str> {
+        match id {
+            1 => Ok(ParameterSet::Small),
+            2 => Ok(ParameterSet::Fast),
+            _ => Err("Unknown parameter set"),
+        }
+    }
+
+    /// Size of a signature in bytes.
+    pub const fn signature_size(self) -> usize {
+        self.params().signature_size()
+    }
+}
+
+/// Bits `0..bits` of `value`.
+fn low_bits(value: u64, bits: u32) -> u64 {
+    if bits >= 64 {
+        value
+    } else {
+        value & ((1u64 << bits) - 1)
+    }
+}
+
+fn be_u64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64)
+}
+
+/// `count` big-endian `bits`-bit values read from `bytes`.
+fn base_2b(bytes: &[u8], bits: u32, count: u32) -> Vec<u32> {
+    let mut input = bytes.iter();
+    let mut acc = 0u64;
+    let mut available = 0;
+    (0..count)
+        .map(|_| {
+            while available < bits {
+                acc = (acc << 8) | *input.next().unwrap() as u64;
+                available += 8;
+            }
+            available -= bits;
+            low_bits(acc >> available, bits) as u32
+        })
+        .collect()
+}
+
+/// FORS indices, hypertree index and leaf index selected by the digest.
+fn split_digest(
+    params: &Params,
+    randomizer: &[u8; N],
+    pk_seed: &[u8; N],
+    root: &[u8; N],
+    message: &[u8],
+) -> (Vec<u32>, u64, u32) {
+    let fors_bytes = params.fors_message_bytes();
+    let tree_end = fors_bytes + params.tree_bytes();
+    let mut digest = vec![0u8; tree_end + params.leaf_bytes()];
+    hash_message(&[randomizer, pk_seed, root], message, &mut digest);
+
+    let indices = base_2b(&digest[..fors_bytes], params.a, params.k);
+    let tree = low_bits(
+        be_u64(&digest[fors_bytes..tree_end]),
+        params.h - params.hp(),
+    );
+    let leaf = low_bits(be_u64(&digest[tree_end..]), params.hp()) as u32;
+    (indices, tree, leaf)
+}
+
+/// FORS leaf at `index`: the hash of its secret value.
+fn fors_leaf(th: &TweakableHash, sk_seed: &[u8; N], address: &Address, index: u32) -> [u8; N] {
+    let address = Address {
+        chain: 0,
+        hash: index,
+        ..*address
+    };
+    let mut secret = th.prf(sk_seed, &address);
+    let leaf = th.hash(&address, &[&secret]);
+    secret.zeroize();
+    leaf
+}
+
+/// Levels of the hypertree subtree at `layer` and `tree`.
+fn subtree(
+    th: &TweakableHash,
+    sk_seed: &[u8; N],
+    layer: u32,
+    tree: u64,
+    height: u32,
+) -> Vec<Vec<[u8; N]>> {
+    let leaves = (0..1u32 << height)
+        .into_par_iter()
+        .map(|keypair| {
+            let address = Address {
+                layer: layer as u8,
+                tree,
+                keypair,
+                ..Address::default()
+            };
+            wots::public_key(th, sk_seed, &address)
+        })
+        .collect();
+    build_levels(th, &tree_address(layer, tree), leaves, 0)
+}
+
+fn tree_address(layer: u32, tree: u64) -> Address {
+    Address {
+        layer: layer as u8,
+        tree,
+        kind: TREE,
+        ..Address::default()
+    }
+}
+
+/// Secret signing key; wiped when dropped.
+///
+/// The key has no state, so it can be copied and used concurrently.
+#[derive(Clone)]
+pub struct SigningKey {
+    set: ParameterSet,
+    sk_seed: [u8; N],
+    sk_prf: [u8; N],
+    pk_seed: [u8; N],
+    root: [u8; N],
+}
+
+impl SigningKey {
+    /// Generates a key from the operating system's random source.
+    pub fn generate(set: ParameterSet) -> Self {
+        let mut seed = [0u8; 3 * N];
+        OsRng.fill_bytes(&mut seed);
+        let key = Self::from_seed(set, &seed);
+        seed.zeroize();
+        key
+    }
+
+    /// Derives a key from a 96-byte seed: secret seed, PRF key and public
+    /// seed.
+    pub fn from_seed(set: ParameterSet, seed: &[u8; 3 * N]) -> Self {
+        let params = set.params();
+        let mut key = Self {
+            set,
+            sk_seed: seed[..N].try_into().unwrap(),
+            sk_prf: seed[N..2 * N].try_into().unwrap(),
+            pk_seed: seed[2 * N..].try_into().unwrap(),
+            root: [0u8; N],
+        };
+        let th = TweakableHash::new(&key.pk_seed);
+        let levels = subtree(&th, &key.sk_seed, params.d - 1, 0, params.hp());
+        key.root = levels[params.hp() as usize][0];
+        key
+    }
+
+    pub fn parameter_set(&self) -> ParameterSet {
+        self.set
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey {
+            set: self.set,
+            pk_seed: self.pk_seed,
+            root: self.root,
+        }
+    }
+
+    /// Deterministic signature: signing the same message twice gives the
+    /// same signature.
+    pub fn sign(&self, message: &[u8]) -> Signature {
+        self.sign_with_randomness(message, &self.pk_seed)
+    }
+
+    /// Randomized signature, with fresh randomness mixed into the message
+    /// randomizer. Prefer this where randomness is available: it keeps
+    /// signing safe against fault attacks that rely on repeated
+    /// computations.
+    pub fn sign_randomized(&self, message: &[u8]) -> Signature {
+        let mut opt_rand = [0u8; N];
+        OsRng.fill_bytes(&mut opt_rand);
+        let signature = self.sign_with_randomness(message, &opt_rand);
+        opt_rand.zeroize();
+        signature
+    }
+
+    fn sign_with_randomness(&self, message: &[u8], opt_rand: &[u8; N]) -> Signature {
+        let params = self.set.params();
+        let th = TweakableHash::new(&self.pk_seed);
+        let randomizer = prf_message(&self.sk_prf, opt_rand, message);
+        let (indices, mut tree, mut leaf) =
+            split_digest(&params, &randomizer, &self.pk_seed, &self.root, message);
+
+        let mut bytes = Vec::with_capacity(params.signature_size());
+        bytes.extend_from_slice(&randomizer);
+
+        // FORS signature of the digest
+        let fors_address = Address {
+            tree,
+            kind: FORS_TREE,
+            keypair: leaf,
+            ..Address::default()
+        };
+        let mut roots = Vec::with_capacity(params.k as usize);
+        for (i, &index) in indices.iter().enumerate() {
+            let offset = (i as u32) << params.a;
+            let secret_address = Address {
+                hash: offset + index,
+                ..fors_address
+            };
+            let mut secret = th.prf(&self.sk_seed, &secret_address);
+            bytes.extend_from_slice(&secret);
+            secret.zeroize();
+
+            let leaves = (offset..offset + (1 << params.a))
+                .into_par_iter()
+                .map(|j| fors_leaf(&th, &self.sk_seed, &fors_address, j))
+                .collect();
+            let levels = build_levels(&th, &fors_address, leaves, offset);
+            for node in auth_path(&levels, index as usize) {
+                bytes.extend_from_slice(&node);
+            }
+            roots.push(levels[params.a as usize][0]);
+        }
+        let mut node = fors_public_key(&th, &fors_address, &roots);
+
+        // Hypertree signature of the FORS public key
+        for layer in 0..params.d {
+            let address = Address {
+                layer: layer as u8,
+                tree,
+                keypair: leaf,
+                ..Address::default()
+            };
+            for chain in wots::sign(&th, &self.sk_seed, &address, &node) {
+                bytes.extend_from_slice(&chain);
+            }
+            let levels = subtree(&th, &self.sk_seed, layer, tree, params.hp());
+            for sibling in auth_path(&levels, leaf as usize) {
+                bytes.extend_from_slice(&sibling);
+            }
+            node = levels[params.hp() as usize][0];
+            leaf = low_bits(tree, params.hp()) as u32;
+            tree >>= params.hp();
+        }
+
+        debug_assert_eq!(bytes.len(), params.signature_size());
+        Signature {
+            set: self.set,
+            bytes,
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; SIGNING_KEY_SIZE] {
+        let mut bytes = [0u8; SIGNING_KEY_SIZE];
+        bytes[0] = self.set.id();
+        bytes[1..1 + N].copy_from_slice(&self.sk_seed);
+        bytes[1 + N..1 + 2 * N].copy_from_slice(&self.sk_prf);
+        bytes[1 + 2 * N..1 + 3 * N].copy_from_slice(&self.pk_seed);
+        bytes[1 + 3 * N..].copy_from_slice(&self.root);
+        bytes
+    }
+
+    /// Restores a key serialized by [`SigningKey::to_bytes`]. The stored
+    /// root is trusted, not recomputed.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != SIGNING_KEY_SIZE {
+            return Err("Invalid signing key length");
+        }
+        Ok(Self {
+            set: ParameterSet::from_id(bytes[0])?,
+            sk_seed: bytes[1..1 + N].try_into().unwrap(),
+            sk_prf: bytes[1 + N..1 + 2 * N].try_into().unwrap(),
+            pk_seed: bytes[1 + 2 * N..1 + 3 * N].try_into().unwrap(),
+            root: bytes[1 + 3 * N..].try_into().unwrap(),
+        })
+    }
+}
+
+impl Drop for SigningKey {
+    fn drop(&mut self) {
+        self.sk_seed.zeroize();
+        self.sk_prf.zeroize();
+    }
+}
+
+fn fors_public_key(th: &TweakableHash, fors_address: &Address, roots: &[[u8; N]]) -> [u8; N] {
+    let inputs: Vec<&[u8]> = roots.iter().map(|root| &root[..]).collect();
+    th.hash(&fors_address.with_kind(FORS_ROOTS), &inputs)
+}
+
+/// Public verification key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyingKey {
+    set: ParameterSet,
+    pk_seed: [u8; N],
+    root: [u8; N],
+}
+
+impl VerifyingKey {
+    pub fn parameter_set(&self) -> ParameterSet {
+        self.set
+    }
+
+    /// Returns `true` if `signature` is a valid signature of `message`.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        if signature.set != self.set {
+            return false;
+        }
+        let params = self.set.params();
+        let th = TweakableHash::new(&self.pk_seed);
+        let mut fields = signature.bytes.chunks_exact(N);
+        let mut next = || -> [u8; N] { fields.next().unwrap().try_into().unwrap() };
+
+        let randomizer = next();
+        let (indices, mut tree, mut leaf) =
+            split_digest(&params, &randomizer, &self.pk_seed, &self.root, message);
+
+        let fors_address = Address {
+            tree,
+            kind: FORS_TREE,
+            keypair: leaf,
+            ..Address::default()
+        };
+        let mut roots = Vec::with_capacity(params.k as usize);
+        for (i, &index) in indices.iter().enumerate() {
+            let index = ((i as u32) << params.a) + index;
+            let secret = next();
+            let path: Vec<[u8; N]> = (0..params.a).map(|_| next()).collect();
+            let leaf_address = Address {
+                chain: 0,
+                hash: index,
+                ..fors_address
+            };
+            let fors_leaf = th.hash(&leaf_address, &[&secret]);
+            roots.push(root_from_auth_path(
+                &th,
+                &fors_address,
+                fors_leaf,
+                index,
+                &path,
+            ));
+        }
+        let mut node = fors_public_key(&th, &fors_address, &roots);
+
+        for layer in 0..params.d {
+            let mut chains = [[0u8; N]; wots::LEN];
+            for chain in chains.iter_mut() {
+                *chain = next();
+            }
+            let path: Vec<[u8; N]> = (0..params.hp()).map(|_| next()).collect();
+            let address = Address {
+                layer: layer as u8,
+                tree,
+                keypair: leaf,
+                ..Address::default()
+            };
+            let wots_pk = wots::public_key_from_signature(&th, &address, &chains, &node);
+            node = root_from_auth_path(&th, &tree_address(layer, tree), wots_pk, leaf, &path);
+            leaf = low_bits(tree, params.hp()) as u32;
+            tree >>= params.hp();
+        }
+        crate::ct_eq(&node, &self.root)
+    }
+
+    pub fn to_bytes(&self) -> [u8; VERIFYING_KEY_SIZE] {
+        let mut bytes = [0u8; VERIFYING_KEY_SIZE];
+        bytes[0] = self.set.id();
+        bytes[1..1 + N].copy_from_slice(&self.pk_seed);
+        bytes[1 + N..].copy_from_slice(&self.root);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != VERIFYING_KEY_SIZE {
+            return Err("Invalid verifying key length");
+        }
+        Ok(Self {
+            set: ParameterSet::from_id(bytes[0])?,
+            pk_seed: bytes[1..1 + N].try_into().unwrap(),
+            root: bytes[1 + N..].try_into().unwrap(),
+        })
+    }
+}
+
+/// Signature produced by [`SigningKey::sign`] or
+/// [`SigningKey::sign_randomized`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    set: ParameterSet,
+    bytes: Vec<u8>,
+}
+
+impl Signature {
+    /// Wraps an encoded signature for the given parameter set.
+    pub fn from_bytes(set: ParameterSet, bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != set.signature_size() {
+            return Err("Invalid signature length");
+        }
+        Ok(Self {
+            set,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn parameter_set(&self) -> ParameterSet {
+        self.set
+    }
+}
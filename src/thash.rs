@@ -0,0 +1,205 @@
+//! Tweakable hash functions for the hash-based signature schemes
+//!
+//! Every hash call in [`crate::wots`], [`crate::xmss`] and
+//! [`crate::sphincs`] is bound to the public seed of its key pair and to an
+//! [`Address`] naming the exact position of the call in the key structure,
+//! so that outputs from different positions or keys can never be
+//! substituted for each other.
+//!
+//! The sponge first absorbs the public seed, zero-padded to a full block;
+//! that state is computed once and cloned for every call. A call then
+//! absorbs the 22-byte compressed address and its inputs, so the common
+//! single-input case fits in one further permutation.
+
+use crate::core::{domain, VortexHash, DEFAULT_CAPACITY};
+use crate::security::DEFAULT_ROUNDS;
+
+/// Output size of the tweakable hash and size of all seeds, in bytes.
+pub const N: usize = 32;
+
+/// Address types, as in XMSS and SPHINCS+.
+pub(crate) const WOTS_HASH: u8 = 0;
+pub(crate) const WOTS_PK: u8 = 1;
+pub(crate) const TREE: u8 = 2;
+pub(crate) const FORS_TREE: u8 = 3;
+pub(crate) const FORS_ROOTS: u8 = 4;
+
+/// Position of a hash call inside a key structure.
+///
+/// Serialized in 22 bytes: layer (1), tree (8), type (1), key pair (4),
+/// chain or tree height (4), hash or tree index (4), all big-endian.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Address {
+    pub layer: u8,
+    pub tree: u64,
+    pub kind: u8,
+    pub keypair: u32,
+    /// Chain number for WOTS addresses, node height for tree addresses.
+    pub chain: u32,
+    /// Step in the chain for WOTS addresses, node index for tree addresses.
+    pub hash: u32,
+}
+
+impl Address {
+    pub const SIZE: usize = 22;
+
+    /// Address of type `kind` for the same layer, tree and key pair, with
+    /// the remaining fields cleared.
+    pub fn with_kind(&self, kind: u8) -> Self {
+        Self {
+            layer: self.layer,
+            tree: self.tree,
+            kind,
+            keypair: self.keypair,
+            ..Self::default()
+        }
+    }
+
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[0] = self.layer;
+        bytes[1..9].copy_from_slice(&self.tree.to_be_bytes());
+        bytes[9] = self.kind;
+        bytes[10..14].copy_from_slice(&self.keypair.to_be_bytes());
+        bytes[14..18].copy_from_slice(&self.chain.to_be_bytes());
+        bytes[18..22].copy_from_slice(&self.hash.to_be_bytes());
+        bytes
+    }
+}
+
+/// Tweakable hash keyed by a public seed.
+#[derive(Clone)]
+pub(crate) struct TweakableHash {
+    prefix: VortexHash,
+}
+
+impl TweakableHash {
+    pub fn new(pk_seed: &[u8; N]) -> Self {
+        let mut prefix = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+        let rate = prefix.rate();
+        prefix.absorb(pk_seed);
+        prefix.absorb(&[0u8; 128][..rate - N]);
+        Self { prefix }
+    }
+
+    /// `T(pk_seed, address, inputs[0] || inputs[1] || ...)`.
+    pub fn hash(&self, address: &Address, inputs: &[&[u8]]) -> [u8; N] {
+        let mut sponge = self.prefix.clone();
+        sponge.absorb(&address.to_bytes());
+        for input in inputs {
+            sponge.absorb(input);
+        }
+        let mut output = [0u8; N];
+        sponge.finalize_into(domain::SIGNATURE, &mut output);
+        output
+    }
+
+    /// Derives the secret value at `address` from `sk_seed`.
+    pub fn prf(&self, sk_seed: &[u8; N], address: &Address) -> [u8; N] {
+        let mut sponge = self.prefix.clone();
+        sponge.absorb(&address.to_bytes());
+        sponge.absorb(sk_seed);
+        let mut output = [0u8; N];
+        sponge.finalize_into(domain::SIGNATURE_PRF, &mut output);
+        output
+    }
+}
+
+/// Message hash: absorbs the fixed-length `prefix` fields, then `message`,
+/// and fills `output`.
+pub(crate) fn hash_message(prefix: &[&[u8]], message: &[u8], output: &mut [u8]) {
+    let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+    for field in prefix {
+        sponge.absorb(field);
+    }
+    sponge.absorb(message);
+    sponge.finalize_into(domain::SIGNATURE_MESSAGE, output);
+}
+
+/// Keyed PRF used for message randomizers: `PRF_msg(sk_prf, opt_rand, msg)`.
+pub(crate) fn prf_message(sk_prf: &[u8; N], opt_rand: &[u8; N], message: &[u8]) -> [u8; N] {
+    let mut output = [0u8; N];
+    let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+    sponge.absorb(sk_prf);
+    sponge.absorb(opt_rand);
+    sponge.absorb(message);
+    sponge.finalize_into(domain::SIGNATURE_RANDOMIZER, &mut output);
+    output
+}
+
+/// Parent of `left` and `right` at `height` and `index`, hashed under
+/// `address` with its height and index fields replaced.
+pub(crate) fn node_hash(
+    th: &TweakableHash,
+    address: &Address,
+    height: u32,
+    index: u32,
+    left: &[u8; N],
+    right: &[u8; N],
+) -> [u8; N] {
+    let address = Address {
+        chain: height,
+        hash: index,
+        ..*address
+    };
+    th.hash(&address, &[left, right])
+}
+
+/// Every level of the Merkle tree over `leaves`, starting with the leaves.
+///
+/// `offset` is the index of the first leaf within its address space, so
+/// several trees can share one (as the FORS trees do).
+pub(crate) fn build_levels(
+    th: &TweakableHash,
+    address: &Address,
+    leaves: Vec<[u8; N]>,
+    offset: u32,
+) -> Vec<Vec<[u8; N]>> {
+    let mut levels = vec![leaves];
+    let mut height = 0;
+    while levels[height].len() > 1 {
+        let below = &levels[height];
+        let first = offset >> (height + 1);
+        let nodes = below
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                let index = first + i as u32;
+                node_hash(th, address, height as u32 + 1, index, &pair[0], &pair[1])
+            })
+            .collect();
+        levels.push(nodes);
+        height += 1;
+    }
+    levels
+}
+
+/// Authentication path of the leaf at position `leaf` in `levels`.
+pub(crate) fn auth_path(levels: &[Vec<[u8; N]>], leaf: usize) -> Vec<[u8; N]> {
+    levels[..levels.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(height, level)| level[(leaf >> height) ^ 1])
+        .collect()
+}
+
+/// Root reached from `leaf` at `index` by hashing along `auth_path`.
+pub(crate) fn root_from_auth_path(
+    th: &TweakableHash,
+    address: &Address,
+    leaf: [u8; N],
+    mut index: u32,
+    auth_path: &[[u8; N]],
+) -> [u8; N] {
+    let mut node = leaf;
+    for (height, sibling) in auth_path.iter().enumerate() {
+        let height = height as u32 + 1;
+        node = if index & 1 == 0 {
+            node_hash(th, address, height, index >> 1, &node, sibling)
+        } else {
+            node_hash(th, address, height, index >> 1, sibling, &node)
+        };
+        index >>= 1;
+    }
+    node
+}
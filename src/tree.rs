@@ -0,0 +1,175 @@
+//! Parallel tree hashing for large inputs
+//!
+//! The input is split into [`CHUNK_SIZE`]-byte chunks (the last one may be
+//! shorter; empty input is a single empty chunk). Each chunk is hashed
+//! independently into a 32-byte chaining value, and the chaining values are
+//! combined pairwise in a binary tree whose left subtree always covers the
+//! largest power-of-two number of chunks, as in BLAKE3:
+//!
+//! ```text
+//! leaf(i, chunk)    = H_leaf(be64(i) || chunk)
+//! parent(l, r)      = H_parent(l || r)
+//! digest(len, root) = H_root(be64(len) || root)
+//! ```
+//!
+//! The three functions are the VortexHash sponge at the 256-bit security
+//! level, finalized with [`domain::TREE_LEAF`], [`domain::TREE_PARENT`] and
+//! [`domain::TREE_ROOT`] respectively. Binding the chunk index into every
+//! leaf and the total length into the root keeps chunks from being moved
+//! or dropped, and the separate root domain keeps the digest from ever
+//! equalling the chaining value of a subtree.
+//!
+//! The result is unrelated to [`crate::hash`] of the same input. Chunks are
+//! hashed on the rayon thread pool, so throughput scales with the number of
+//! cores once the input is a few chunks long; the digest does not depend on
+//! the number of threads.
+
+use crate::core::{domain, VortexHash, DEFAULT_CAPACITY};
+use crate::security::DEFAULT_ROUNDS;
+use rayon::prelude::*;
+
+/// Size of a leaf chunk in bytes.
+pub const CHUNK_SIZE: usize = 8192;
+
+/// Size of a chaining value in bytes.
+pub const CV_SIZE: usize = 32;
+
+/// Number of chunks [`TreeHash`] buffers before hashing them in parallel.
+const BATCH_CHUNKS: usize = 32;
+
+const BATCH_SIZE: usize = BATCH_CHUNKS * CHUNK_SIZE;
+
+/// Chaining value of the chunk at position `index`.
+pub(crate) fn leaf_cv(index: u64, chunk: &[u8]) -> [u8; CV_SIZE] {
+    debug_assert!(chunk.len() <= CHUNK_SIZE);
+    let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+    sponge.absorb(&index.to_be_bytes());
+    sponge.absorb(chunk);
+    let mut cv = [0u8; CV_SIZE];
+    sponge.finalize_into(domain::TREE_LEAF, &mut cv);
+    cv
+}
+
+/// Chaining value of the parent of `left` and `right`.
+pub(crate) fn parent_cv(left: &[u8; CV_SIZE], right: &[u8; CV_SIZE]) -> [u8; CV_SIZE] {
+    let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+    sponge.absorb(left);
+    sponge.absorb(right);
+    let mut cv = [0u8; CV_SIZE];
+    sponge.finalize_into(domain::TREE_PARENT, &mut cv);
+    cv
+}
+
+/// Digest of a `length`-byte input whose tree has chaining value `root`.
+pub(crate) fn root_digest(length: u64, root: &[u8; CV_SIZE]) -> [u8; 32] {
+    let mut sponge = VortexHash::with_capacity(DEFAULT_CAPACITY, DEFAULT_ROUNDS);
+    sponge.absorb(&length.to_be_bytes());
+    sponge.absorb(root);
+    let mut digest = [0u8; 32];
+    sponge.finalize_into(domain::TREE_ROOT, &mut digest);
+    digest
+}
+
+/// Streaming tree hasher.
+///
+/// Input is buffered until a batch of chunks is available, and each batch
+/// is hashed in parallel, so feeding it small writes (for example from a
+/// file reader) still uses every core.
+///
+/// # Examples
+///
+/// ```
+/// use vortex_hash::TreeHash;
+///
+/// let data = vec![0x5au8; 100_000];
+/// let mut hasher = TreeHash::new();
+/// for piece in data.chunks(4096) {
+///     hasher.update(piece);
+/// }
+/// assert_eq!(hasher.finalize(), TreeHash::digest(&data));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TreeHash {
+    buffer: Vec<u8>,
+    /// Chaining values of the complete subtrees so far, largest first.
+    stack: Vec<[u8; CV_SIZE]>,
+    chunks: u64, // Chunks hashed so far
+    length: u64, // Bytes absorbed so far
+}
+
+impl TreeHash {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// One-shot digest of `data`.
+    pub fn digest(data: &[u8]) -> [u8; 32] {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.length += data.len() as u64;
+        if !self.buffer.is_empty() {
+            let take = (BATCH_SIZE - self.buffer.len()).min(data.len());
+            self.buffer.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.buffer.len() < BATCH_SIZE {
+                return;
+            }
+            let buffer = std::mem::take(&mut self.buffer);
+            self.add_chunks(&buffer);
+            self.buffer = buffer;
+            self.buffer.clear();
+        }
+        if data.len() >= BATCH_SIZE {
+            let whole = data.len() - data.len() % CHUNK_SIZE;
+            self.add_chunks(&data[..whole]);
+            data = &data[whole..];
+        }
+        self.buffer.extend_from_slice(data);
+    }
+
+    pub fn finalize(mut self) -> [u8; 32] {
+        if !self.buffer.is_empty() || self.chunks == 0 {
+            let buffer = std::mem::take(&mut self.buffer);
+            self.add_chunks(&buffer);
+        }
+        let mut root = self.stack.pop().expect("at least one chunk");
+        while let Some(left) = self.stack.pop() {
+            root = parent_cv(&left, &root);
+        }
+        root_digest(self.length, &root)
+    }
+
+    /// Hashes `data` as consecutive chunks in parallel. Every chunk but the
+    /// last must be full, and only the final call may pass a short chunk.
+    fn add_chunks(&mut self, data: &[u8]) {
+        let first = self.chunks;
+        let cvs: Vec<[u8; CV_SIZE]> = if data.is_empty() {
+            vec![leaf_cv(first, data)]
+        } else {
+            data.par_chunks(CHUNK_SIZE)
+                .enumerate()
+                .map(|(i, chunk)| leaf_cv(first + i as u64, chunk))
+                .collect()
+        };
+        for cv in cvs {
+            self.push_cv(cv);
+        }
+    }
+
+    /// Pushes the next chunk's chaining value, merging every subtree that
+    /// it completes.
+    fn push_cv(&mut self, mut cv: [u8; CV_SIZE]) {
+        self.chunks += 1;
+        let mut total = self.chunks;
+        while total & 1 == 0 {
+            let left = self.stack.pop().expect("left subtree");
+            cv = parent_cv(&left, &cv);
+            total >>= 1;
+        }
+        self.stack.push(cv);
+    }
+}
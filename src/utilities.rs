@@ -15,6 +15,51 @@ pub fn validate_input(data: &[u8]) -> bool {
     !data.is_empty()
 }
 
+/// Lowercase hexadecimal encoding of `data`.
+pub fn to_hex(data: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(data.len() * 2);
+    for &byte in data {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    out
+}
+
+/// Decodes lowercase hexadecimal, the inverse of [`to_hex`].
+pub fn from_hex(text: &str) -> Result<Vec<u8>, &'static str> {
+    fn nibble(c: u8) -> Result<u8, &'static str> {
+        match c {
+            b'0'..=b'9' => Ok(c - b'0'),
+            b'a'..=b'f' => Ok(c - b'a' + 10),
+            _ => Err("Invalid hex digit"),
+        }
+    }
+    if !text.len().is_multiple_of(2) {
+        return Err("Odd number of hex digits");
+    }
+    text.as_bytes()
+        .chunks(2)
+        .map(|pair| Ok(nibble(pair[0])? << 4 | nibble(pair[1])?))
+        .collect()
+}
+
+/// Parses a `name=<decimal>` parameter of a PHC-style string, rejecting
+/// signs and leading zeros so each value has one representation.
+pub(crate) fn parse_field(field: &str, name: &str) -> Result<u32, &'static str> {
+    let value = field
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix('='))
+        .ok_or("Malformed parameter field")?;
+    let canonical = !value.is_empty()
+        && value.bytes().all(|b| b.is_ascii_digit())
+        && (value == "0" || !value.starts_with('0'));
+    if !canonical {
+        return Err("Malformed parameter value");
+    }
+    value.parse().map_err(|_| "Parameter out of range")
+}
+
 pub mod constant_time {
     pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
         if a.len() != b.len() {
@@ -0,0 +1,86 @@
+//! Fixed-length VortexHash variants
+//!
+//! Each variant has its own sponge capacity (twice the digest size, capped
+//! at 896 bits) and its own domain-separation byte, so a truncated
+//! [`VortexHash512`] digest is unrelated to a [`VortexHash256`] digest of
+//! the same input. All variants use [`DEFAULT_ROUNDS`] permutation rounds.
+//!
+//! [`VortexHash256`] computes the same function as [`crate::hash`].
+
+use crate::core::{domain, VortexHash};
+use crate::security::DEFAULT_ROUNDS;
+
+macro_rules! define_variant {
+    ($(#[$attr:meta])* $name:ident, $size:expr, $capacity:expr, $domain:expr) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone)]
+        pub struct $name {
+            sponge: VortexHash,
+        }
+
+        impl $name {
+            /// Digest size in bytes.
+            pub const OUTPUT_SIZE: usize = $size;
+            /// Sponge capacity in bytes.
+            pub const CAPACITY: usize = $capacity;
+
+            pub fn new() -> Self {
+                Self {
+                    sponge: VortexHash::with_capacity(Self::CAPACITY, DEFAULT_ROUNDS),
+                }
+            }
+
+            pub fn update(&mut self, data: &[u8]) {
+                self.sponge.absorb(data);
+            }
+
+            pub fn finalize(mut self) -> [u8; $size] {
+                let mut output = [0u8; $size];
+                self.sponge.finalize_into($domain, &mut output);
+                output
+            }
+
+            /// One-shot digest of `data`.
+            pub fn digest(data: &[u8]) -> [u8; $size] {
+                let mut hasher = Self::new();
+                hasher.update(data);
+                hasher.finalize()
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+define_variant!(
+    /// VortexHash with a 224-bit digest and a 448-bit capacity.
+    VortexHash224,
+    28,
+    56,
+    domain::HASH_224
+);
+define_variant!(
+    /// VortexHash with a 256-bit digest and a 512-bit capacity.
+    VortexHash256,
+    32,
+    64,
+    domain::HASH
+);
+define_variant!(
+    /// VortexHash with a 384-bit digest and a 768-bit capacity.
+    VortexHash384,
+    48,
+    96,
+    domain::HASH_384
+);
+define_variant!(
+    /// VortexHash with a 512-bit digest and an 896-bit capacity.
+    VortexHash512,
+    64,
+    112,
+    domain::HASH_512
+);
@@ -0,0 +1,248 @@
+//! Winternitz one-time signatures (WOTS+)
+//!
+//! Each key consists of [`LEN`] hash chains of length [`W`]. The message
+//! digest is split into base-`W` digits, followed by the digits of their
+//! checksum, and each digit selects how far along its chain the signature
+//! reveals. Chains and the compressed public key use the tweakable hash of
+//! [`crate::thash`], so every step is bound to the public seed and to its
+//! position.
+//!
+//! A WOTS key must sign a single message: two signatures reveal enough
+//! chain values to forge others. [`WotsKeyPair::sign`] therefore consumes
+//! the key pair. For many-time keys see [`crate::xmss`].
+//!
+//! # Examples
+//!
+//! ```
+//! use vortex_hash::wots::WotsKeyPair;
+//!
+//! let key_pair = WotsKeyPair::generate();
+//! let public_key = key_pair.public_key();
+//! let signature = key_pair.sign(b"message");
+//! assert!(public_key.verify(b"message", &signature));
+//! ```
+
+pub use crate::thash::N;
+use crate::thash::{hash_message, Address, TweakableHash, WOTS_HASH, WOTS_PK};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use zeroize::Zeroize;
+
+/// Winternitz parameter: each chain has `W - 1` steps.
+pub const W: u32 = 16;
+/// Number of message digits (4 bits each).
+pub const LEN1: usize = 2 * N;
+/// Number of checksum digits.
+pub const LEN2: usize = 3;
+/// Total number of chains.
+pub const LEN: usize = LEN1 + LEN2;
+/// Size of a serialized [`WotsSignature`].
+pub const SIGNATURE_SIZE: usize = LEN * N;
+/// Size of a serialized [`WotsPublicKey`].
+pub const PUBLIC_KEY_SIZE: usize = 2 * N;
+
+/// Base-`W` digits of `digest` followed by those of its checksum.
+fn digits(digest: &[u8; N]) -> [u32; LEN] {
+    let mut out = [0u32; LEN];
+    for (i, byte) in digest.iter().enumerate() {
+        out[2 * i] = (byte >> 4) as u32;
+        out[2 * i + 1] = (byte & 0x0f) as u32;
+    }
+    let checksum: u32 = out[..LEN1].iter().map(|d| W - 1 - d).sum();
+    for i in 0..LEN2 {
+        out[LEN1 + i] = (checksum >> (4 * (LEN2 - 1 - i))) & 0x0f;
+    }
+    out
+}
+
+/// Applies `steps` chain steps to `value`, starting at position `start`.
+fn chain(th: &TweakableHash, address: &mut Address, value: &mut [u8; N], start: u32, steps: u32) {
+    for step in start..start + steps {
+        address.hash = step;
+        *value = th.hash(address, &[&value[..]]);
+    }
+}
+
+/// Compresses the chain ends into the public key of the key pair at
+/// `address`.
+fn compress(th: &TweakableHash, address: &Address, ends: &[[u8; N]; LEN]) -> [u8; N] {
+    let inputs: Vec<&[u8]> = ends.iter().map(|end| &end[..]).collect();
+    th.hash(&address.with_kind(WOTS_PK), &inputs)
+}
+
+/// Public key (chain ends, compressed) of the WOTS key pair at `address`.
+pub(crate) fn public_key(th: &TweakableHash, sk_seed: &[u8; N], address: &Address) -> [u8; N] {
+    let mut ends = [[0u8; N]; LEN];
+    let mut chain_address = address.with_kind(WOTS_HASH);
+    for (i, end) in ends.iter_mut().enumerate() {
+        chain_address.chain = i as u32;
+        chain_address.hash = 0;
+        *end = th.prf(sk_seed, &chain_address);
+        chain(th, &mut chain_address, end, 0, W - 1);
+    }
+    let pk = compress(th, address, &ends);
+    ends.zeroize();
+    pk
+}
+
+/// Signs a `digest` with the WOTS key pair at `address`.
+pub(crate) fn sign(
+    th: &TweakableHash,
+    sk_seed: &[u8; N],
+    address: &Address,
+    digest: &[u8; N],
+) -> [[u8; N]; LEN] {
+    let mut signature = [[0u8; N]; LEN];
+    let mut chain_address = address.with_kind(WOTS_HASH);
+    for (i, (value, digit)) in signature.iter_mut().zip(digits(digest)).enumerate() {
+        chain_address.chain = i as u32;
+        chain_address.hash = 0;
+        *value = th.prf(sk_seed, &chain_address);
+        chain(th, &mut chain_address, value, 0, digit);
+    }
+    signature
+}
+
+/// Recomputes the public key of the key pair at `address` from a signature
+/// of `digest`.
+pub(crate) fn public_key_from_signature(
+    th: &TweakableHash,
+    address: &Address,
+    signature: &[[u8; N]; LEN],
+    digest: &[u8; N],
+) -> [u8; N] {
+    let mut ends = *signature;
+    let mut chain_address = address.with_kind(WOTS_HASH);
+    for (i, (end, digit)) in ends.iter_mut().zip(digits(digest)).enumerate() {
+        chain_address.chain = i as u32;
+        chain(th, &mut chain_address, end, digit, W - 1 - digit);
+    }
+    compress(th, address, &ends)
+}
+
+/// Digest of `message` under a standalone WOTS public key.
+fn message_digest(pk_seed: &[u8; N], pk: &[u8; N], message: &[u8]) -> [u8; N] {
+    let mut digest = [0u8; N];
+    hash_message(&[pk_seed, pk], message, &mut digest);
+    digest
+}
+
+/// Secret key of a standalone WOTS key pair; wiped when dropped.
+pub struct WotsKeyPair {
+    sk_seed: [u8; N],
+    pk_seed: [u8; N],
+}
+
+impl WotsKeyPair {
+    /// Generates a key pair from the operating system's random source.
+    pub fn generate() -> Self {
+        let mut seed = [0u8; 2 * N];
+        OsRng.fill_bytes(&mut seed);
+        let key_pair = Self::from_seed(&seed);
+        seed.zeroize();
+        key_pair
+    }
+
+    /// Derives a key pair from a 64-byte seed: the secret seed followed by
+    /// the public seed.
+    pub fn from_seed(seed: &[u8; 2 * N]) -> Self {
+        let mut sk_seed = [0u8; N];
+        let mut pk_seed = [0u8; N];
+        sk_seed.copy_from_slice(&seed[..N]);
+        pk_seed.copy_from_slice(&seed[N..]);
+        Self { sk_seed, pk_seed }
+    }
+
+    pub fn public_key(&self) -> WotsPublicKey {
+        let th = TweakableHash::new(&self.pk_seed);
+        WotsPublicKey {
+            pk_seed: self.pk_seed,
+            pk: public_key(&th, &self.sk_seed, &Address::default()),
+        }
+    }
+
+    /// Signs `message`, consuming the key pair so it cannot sign again.
+    pub fn sign(self, message: &[u8]) -> WotsSignature {
+        let th = TweakableHash::new(&self.pk_seed);
+        let address = Address::default();
+        let pk = public_key(&th, &self.sk_seed, &address);
+        let digest = message_digest(&self.pk_seed, &pk, message);
+        WotsSignature {
+            chains: sign(&th, &self.sk_seed, &address, &digest),
+        }
+    }
+}
+
+impl Drop for WotsKeyPair {
+    fn drop(&mut self) {
+        self.sk_seed.zeroize();
+    }
+}
+
+/// Public key of a standalone WOTS key pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WotsPublicKey {
+    pk_seed: [u8; N],
+    pk: [u8; N],
+}
+
+impl WotsPublicKey {
+    /// Returns `true` if `signature` is a valid signature of `message`.
+    pub fn verify(&self, message: &[u8], signature: &WotsSignature) -> bool {
+        let th = TweakableHash::new(&self.pk_seed);
+        let digest = message_digest(&self.pk_seed, &self.pk, message);
+        let pk = public_key_from_signature(&th, &Address::default(), &signature.chains, &digest);
+        crate::ct_eq(&pk, &self.pk)
+    }
+
+    /// Public seed followed by the compressed public key.
+    pub fn to_bytes(&self) -> [u8; PUBLIC_KEY_SIZE] {
+        let mut bytes = [0u8; PUBLIC_KEY_SIZE];
+        bytes[..N].copy_from_slice(&self.pk_seed);
+        bytes[N..].copy_from_slice(&self.pk);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != PUBLIC_KEY_SIZE {
+            return Err("Invalid WOTS public key length");
+        }
+        let mut key = Self {
+            pk_seed: [0u8; N],
+            pk: [0u8; N],
+        };
+        key.pk_seed.copy_from_slice(&bytes[..N]);
+        key.pk.copy_from_slice(&bytes[N..]);
+        Ok(key)
+    }
+}
+
+/// WOTS signature: one chain value per digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WotsSignature {
+    chains: [[u8; N]; LEN],
+}
+
+impl WotsSignature {
+    /// The chain values, concatenated.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.chains.concat()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        Ok(Self {
+            chains: chains_from_bytes(bytes)?,
+        })
+    }
+}
+
+pub(crate) fn chains_from_bytes(bytes: &[u8]) -> Result<[[u8; N]; LEN], &'static str> {
+    if bytes.len() != SIGNATURE_SIZE {
+        return Err("Invalid WOTS signature length");
+    }
+    let mut chains = [[0u8; N]; LEN];
+    for (value, chunk) in chains.iter_mut().zip(bytes.chunks_exact(N)) {
+        value.copy_from_slice(chunk);
+    }
+    Ok(chains)
+}
@@ -0,0 +1,371 @@
+//! Stateful many-time signatures (XMSS-style Merkle tree of WOTS keys)
+//!
+//! A key of height `h` holds `2^h` [`crate::wots`] key pairs, all derived
+//! from one secret seed. Their public keys are the leaves of a Merkle tree
+//! whose root, with the public seed, is the public key. A signature carries
+//! the leaf index, a message randomizer, the WOTS signature and the
+//! authentication path to the root.
+//!
+//! Each leaf may sign only once. [`SigningKey`] tracks the next unused
+//! index, advances it before every signature and refuses to sign once all
+//! leaves are used. The index is part of [`SigningKey::to_bytes`]: persist
+//! the key after every call to [`SigningKey::sign`] and before releasing
+//! the signature, and never restore an older copy. Deployments that cannot
+//! guarantee this should use a stateless scheme instead.
+//!
+//! # Wire format
+//!
+//! ```text
+//! VerifyingKey: height (1) || pk_seed (32) || root (32)
+//! Signature:    index (4, big-endian) || randomizer (32)
+//!               || WOTS signature (67 * 32) || auth path (height * 32)
+//! SigningKey:   height (1) || next index (4, big-endian) || sk_seed (32)
+//!               || sk_prf (32) || pk_seed (32) || root (32)
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use vortex_hash::xmss::SigningKey;
+//!
+//! let mut signing_key = SigningKey::generate(4).unwrap();
+//! let verifying_key = signing_key.verifying_key();
+//! let signature = signing_key.sign(b"message").unwrap();
+//! assert!(verifying_key.verify(b"message", &signature));
+//! assert_eq!(signing_key.remaining(), 15);
+//! ```
+
+use crate::thash::{
+    auth_path, build_levels, hash_message, prf_message, root_from_auth_path, Address,
+    TweakableHash, N, TREE,
+};
+use crate::wots;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use zeroize::Zeroize;
+
+/// Smallest supported tree height.
+pub const MIN_HEIGHT: u8 = 1;
+/// Largest supported tree height (about a million signatures).
+pub const MAX_HEIGHT: u8 = 20;
+/// Height used by [`SigningKey::generate_default`].
+pub const DEFAULT_HEIGHT: u8 = 10;
+/// Size of a serialized [`VerifyingKey`].
+pub const VERIFYING_KEY_SIZE: usize = 1 + 2 * N;
+/// Size of a serialized [`SigningKey`].
+pub const SIGNING_KEY_SIZE: usize = 5 + 4 * N;
+
+/// Size of a serialized [`Signature`] for a tree of the given height.
+pub const fn signature_size(height: u8) -> usize {
+    4 + N + wots::SIGNATURE_SIZE + height as usize * N
+}
+
+/// Address of the Merkle tree nodes.
+const TREE_ADDRESS: Address = Address {
+    layer: 0,
+    tree: 0,
+    kind: TREE,
+    keypair: 0,
+    chain: 0,
+    hash: 0,
+};
+
+fn check_height(height: u8) -> Result<(), &'static str> {
+    if (MIN_HEIGHT..=MAX_HEIGHT).contains(&height) {
+        Ok(())
+    } else {
+        Err("Unsupported tree height")
+    }
+}
+
+/// Every node of the tree, level by level, starting with the leaves.
+fn build_tree(th: &TweakableHash, sk_seed: &[u8; N], height: u8) -> Vec<Vec<[u8; N]>> {
+    let mut leaf_address = Address::default();
+    let leaves = (0..1u32 << height)
+        .map(|i| {
+            leaf_address.keypair = i;
+            wots::public_key(th, sk_seed, &leaf_address)
+        })
+        .collect();
+    build_levels(th, &TREE_ADDRESS, leaves, 0)
+}
+
+/// Digest signed by the WOTS key at `index`.
+fn message_digest(
+    randomizer: &[u8; N],
+    pk_seed: &[u8; N],
+    root: &[u8; N],
+    index: u32,
+    message: &[u8],
+) -> [u8; N] {
+    let mut digest = [0u8; N];
+    let index = (index as u64).to_be_bytes();
+    hash_message(&[randomizer, pk_seed, root, &index], message, &mut digest);
+    digest
+}
+
+/// Secret signing key with its usage state.
+///
+/// Not `Clone`: a copy would let both instances sign with the same leaves.
+pub struct SigningKey {
+    height: u8,
+    next_index: u32,
+    sk_seed: [u8; N],
+    sk_prf: [u8; N],
+    pk_seed: [u8; N],
+    tree: Vec<Vec<[u8; N]>>,
+}
+
+impl SigningKey {
+    /// Generates a key with `2^height` one-time leaves from the operating
+    /// system's random source.
+    ///
+    /// Key generation computes every WOTS public key, so its cost grows
+    /// linearly with the number of leaves.
+    pub fn generate(height: u8) -> Result<Self, &'static str> {
+        let mut seed = [0u8; 3 * N];
+        OsRng.fill_bytes(&mut seed);
+        let key = Self::from_seed(&seed, height);
+        seed.zeroize();
+        key
+    }
+
+    /// [`SigningKey::generate`] with [`DEFAULT_HEIGHT`].
+    pub fn generate_default() -> Result<Self, &'static str> {
+        Self::generate(DEFAULT_HEIGHT)
+    }
+
+    /// Derives a key from a 96-byte seed: secret seed, PRF key and public
+    /// seed.
+    pub fn from_seed(seed: &[u8; 3 * N], height: u8) -> Result<Self, &'static str> {
+        check_height(height)?;
+        let mut sk_seed = [0u8; N];
+        let mut sk_prf = [0u8; N];
+        let mut pk_seed = [0u8; N];
+        sk_seed.copy_from_slice(&seed[..N]);
+        sk_prf.copy_from_slice(&seed[N..2 * N]);
+        pk_seed.copy_from_slice(&seed[2 * N..]);
+        let tree = build_tree(&TweakableHash::new(&pk_seed), &sk_seed, height);
+        Ok(Self {
+            height,
+            next_index: 0,
+            sk_seed,
+            sk_prf,
+            pk_seed,
+            tree,
+        })
+    }
+
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    /// Index of the leaf the next signature will use.
+    pub fn next_index(&self) -> u32 {
+        self.next_index
+    }
+
+    /// Number of signatures this key can still produce.
+    pub fn remaining(&self) -> u32 {
+        (1u32 << self.height) - self.next_index
+    }
+
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey {
+            height: self.height,
+            pk_seed: self.pk_seed,
+            root: self.root(),
+        }
+    }
+
+    fn root(&self) -> [u8; N] {
+        self.tree[self.height as usize][0]
+    }
+
+    /// Signs `message` with the next unused leaf.
+    ///
+    /// The index is advanced before the signature is computed. Returns an
+    /// error once every leaf has been used.
+    pub fn sign(&mut self, message: &[u8]) -> Result<Signature, &'static str> {
+        if self.remaining() == 0 {
+            return Err("Signing key exhausted");
+        }
+        let index = self.next_index;
+        self.next_index += 1;
+
+        let mut index_block = [0u8; N];
+        index_block[N - 4..].copy_from_slice(&index.to_be_bytes());
+        let randomizer = prf_message(&self.sk_prf, &index_block, message);
+        let root = self.root();
+        let digest = message_digest(&randomizer, &self.pk_seed, &root, index, message);
+
+        let th = TweakableHash::new(&self.pk_seed);
+        let address = Address {
+            keypair: index,
+            ..Address::default()
+        };
+        Ok(Signature {
+            index,
+            randomizer,
+            wots: wots::sign(&th, &self.sk_seed, &address, &digest),
+            auth_path: auth_path(&self.tree, index as usize),
+        })
+    }
+
+    /// Serializes the key, including its current index.
+    pub fn to_bytes(&self) -> [u8; SIGNING_KEY_SIZE] {
+        let mut bytes = [0u8; SIGNING_KEY_SIZE];
+        bytes[0] = self.height;
+        bytes[1..5].copy_from_slice(&self.next_index.to_be_bytes());
+        bytes[5..5 + N].copy_from_slice(&self.sk_seed);
+        bytes[5 + N..5 + 2 * N].copy_from_slice(&self.sk_prf);
+        bytes[5 + 2 * N..5 + 3 * N].copy_from_slice(&self.pk_seed);
+        bytes[5 + 3 * N..].copy_from_slice(&self.root());
+        bytes
+    }
+
+    /// Restores a key serialized by [`SigningKey::to_bytes`], rebuilding
+    /// its tree. Fails if the stored root does not match the seeds.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != SIGNING_KEY_SIZE {
+            return Err("Invalid signing key length");
+        }
+        let height = bytes[0];
+        let next_index = u32::from_be_bytes(bytes[1..5].try_into().unwrap());
+        let mut seed = [0u8; 3 * N];
+        seed.copy_from_slice(&bytes[5..5 + 3 * N]);
+        let key = Self::from_seed(&seed, height);
+        seed.zeroize();
+        let mut key = key?;
+        if key.root()[..] != bytes[5 + 3 * N..] {
+            return Err("Signing key root does not match its seeds");
+        }
+        if next_index as u64 > 1u64 << height {
+            return Err("Invalid signing key index");
+        }
+        key.next_index = next_index;
+        Ok(key)
+    }
+}
+
+impl Drop for SigningKey {
+    fn drop(&mut self) {
+        self.sk_seed.zeroize();
+        self.sk_prf.zeroize();
+    }
+}
+
+/// Public verification key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyingKey {
+    height: u8,
+    pk_seed: [u8; N],
+    root: [u8; N],
+}
+
+impl VerifyingKey {
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    /// Returns `true` if `signature` is a valid signature of `message`.
+    pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
+        if signature.auth_path.len() != self.height as usize
+            || signature.index as u64 >= 1u64 << self.height
+        {
+            return false;
+        }
+        let th = TweakableHash::new(&self.pk_seed);
+        let digest = message_digest(
+            &signature.randomizer,
+            &self.pk_seed,
+            &self.root,
+            signature.index,
+            message,
+        );
+        let address = Address {
+            keypair: signature.index,
+            ..Address::default()
+        };
+        let leaf = wots::public_key_from_signature(&th, &address, &signature.wots, &digest);
+        let root = root_from_auth_path(
+            &th,
+            &TREE_ADDRESS,
+            leaf,
+            signature.index,
+            &signature.auth_path,
+        );
+        crate::ct_eq(&root, &self.root)
+    }
+
+    pub fn to_bytes(&self) -> [u8; VERIFYING_KEY_SIZE] {
+        let mut bytes = [0u8; VERIFYING_KEY_SIZE];
+        bytes[0] = self.height;
+        bytes[1..1 + N].copy_from_slice(&self.pk_seed);
+        bytes[1 + N..].copy_from_slice(&self.root);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        if bytes.len() != VERIFYING_KEY_SIZE {
+            return Err("Invalid verifying key length");
+        }
+        check_height(bytes[0])?;
+        let mut key = Self {
+            height: bytes[0],
+            pk_seed: [0u8; N],
+            root: [0u8; N],
+        };
+        key.pk_seed.copy_from_slice(&bytes[1..1 + N]);
+        key.root.copy_from_slice(&bytes[1 + N..]);
+        Ok(key)
+    }
+}
+
+/// Signature produced by [`SigningKey::sign`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    index: u32,
+    randomizer: [u8; N],
+    wots: [[u8; N]; wots::LEN],
+    auth_path: Vec<[u8; N]>,
+}
+
+impl Signature {
+    /// Index of the leaf that produced the signature.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(signature_size(self.auth_path.len() as u8));
+        bytes.extend_from_slice(&self.index.to_be_bytes());
+        bytes.extend_from_slice(&self.randomizer);
+        for value in self.wots.iter().chain(&self.auth_path) {
+            bytes.extend_from_slice(value);
+        }
+        bytes
+    }
+
+    /// Parses a signature; the tree height is implied by the length.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let fixed = signature_size(0);
+        if bytes.len() < fixed || !(bytes.len() - fixed).is_multiple_of(N) {
+            return Err("Invalid signature length");
+        }
+        let height = (bytes.len() - fixed) / N;
+        check_height(height.try_into().map_err(|_| "Unsupported tree height")?)?;
+        let mut randomizer = [0u8; N];
+        randomizer.copy_from_slice(&bytes[4..4 + N]);
+        let wots_end = 4 + N + wots::SIGNATURE_SIZE;
+        Ok(Self {
+            index: u32::from_be_bytes(bytes[..4].try_into().unwrap()),
+            randomizer,
+            wots: wots::chains_from_bytes(&bytes[4 + N..wots_end])?,
+            auth_path: bytes[wots_end..]
+                .chunks_exact(N)
+                .map(|chunk| chunk.try_into().unwrap())
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,134 @@
+// Authenticated encryption tests
+use aead::{Aead, AeadInPlace, KeyInit, Payload};
+use vortex_hash::aead::{VortexAead, TAG_SIZE};
+use vortex_hash::kat::AEAD_VECTORS;
+use vortex_hash::to_hex;
+
+const KEY: [u8; 32] = [0x42; 32];
+const NONCE: [u8; 16] = [0x24; 16];
+
+#[test]
+fn test_aead_vectors() {
+    for vector in AEAD_VECTORS {
+        let cipher = VortexAead::new(vector.key);
+        let ciphertext = cipher.encrypt(vector.nonce, vector.associated_data, vector.plaintext);
+        assert_eq!(to_hex(&ciphertext), vector.ciphertext);
+        let plaintext = cipher
+            .decrypt(vector.nonce, vector.associated_data, &ciphertext)
+            .unwrap();
+        assert_eq!(plaintext, vector.plaintext);
+    }
+}
+
+#[test]
+fn test_round_trip_lengths() {
+    let cipher = VortexAead::new(&KEY);
+    for length in [0, 1, 63, 64, 65, 128, 300] {
+        let plaintext: Vec<u8> = (0..length).map(|i| i as u8).collect();
+        let mut buffer = plaintext.clone();
+        let tag = cipher.encrypt_in_place_detached(&NONCE, b"ad", &mut buffer);
+        if length > 0 {
+            assert_ne!(buffer, plaintext);
+        }
+        cipher
+            .decrypt_in_place_detached(&NONCE, b"ad", &mut buffer, &tag)
+            .unwrap();
+        assert_eq!(buffer, plaintext, "length {}", length);
+    }
+}
+
+#[test]
+fn test_tampering_fails_closed() {
+    let cipher = VortexAead::new(&KEY);
+    let plaintext = [0x11u8; 80];
+    let ciphertext = cipher.encrypt(&NONCE, b"header", &plaintext);
+
+    for position in [0, 40, 79, 80, ciphertext.len() - 1] {
+        let mut tampered = ciphertext.clone();
+        tampered[position] ^= 0x01;
+        assert!(cipher.decrypt(&NONCE, b"header", &tampered).is_err());
+    }
+    assert!(cipher.decrypt(&NONCE, b"headeR", &ciphertext).is_err());
+    assert!(cipher
+        .decrypt(&NONCE, b"header", &ciphertext[..TAG_SIZE - 1])
+        .is_err());
+
+    let mut buffer = ciphertext[..80].to_vec();
+    let mut tag: [u8; TAG_SIZE] = ciphertext[80..].try_into().unwrap();
+    tag[0] ^= 0x80;
+    assert!(cipher
+        .decrypt_in_place_detached(&NONCE, b"header", &mut buffer, &tag)
+        .is_err());
+    assert_eq!(buffer, [0u8; 80]);
+}
+
+#[test]
+fn test_associated_data_and_message_are_separated() {
+    let cipher = VortexAead::new(&KEY);
+    let a = cipher.encrypt(&NONCE, b"ab", b"");
+    let b = cipher.encrypt(&NONCE, b"a", b"b");
+    let c = cipher.encrypt(&NONCE, b"", b"ab");
+    assert_ne!(a[a.len() - TAG_SIZE..], b[b.len() - TAG_SIZE..]);
+    assert_ne!(b[b.len() - TAG_SIZE..], c[c.len() - TAG_SIZE..]);
+}
+
+#[test]
+fn test_nonce_misuse() {
+    let cipher = VortexAead::new(&KEY);
+    let first = cipher.encrypt(&NONCE, b"", b"message one");
+    let second = cipher.encrypt(&NONCE, b"", b"message two");
+
+    // Reusing a nonce leaks the XOR of the plaintexts in the first block.
+    let leaked: Vec<u8> = first
+        .iter()
+        .zip(&second)
+        .map(|(a, b)| a ^ b)
+        .take(11)
+        .collect();
+    let expected: Vec<u8> = b"message one"
+        .iter()
+        .zip(b"message two")
+        .map(|(a, b)| a ^ b)
+        .collect();
+    assert_eq!(leaked, expected);
+
+    // A different nonce gives an unrelated ciphertext, and a ciphertext only
+    // opens under its own nonce.
+    let mut other_nonce = NONCE;
+    other_nonce[15] ^= 1;
+    assert_ne!(cipher.encrypt(&other_nonce, b"", b"message one"), first);
+    assert!(cipher.decrypt(&other_nonce, b"", &first).is_err());
+    assert!(VortexAead::new(&[0x43; 32])
+        .decrypt(&NONCE, b"", &first)
+        .is_err());
+}
+
+#[test]
+fn test_aead_traits() {
+    let cipher = <VortexAead as KeyInit>::new(&KEY.into());
+    let nonce = NONCE.into();
+    let payload = Payload {
+        msg: b"through the trait",
+        aad: b"aad",
+    };
+    let ciphertext = Aead::encrypt(&cipher, &nonce, payload).unwrap();
+    assert_eq!(
+        ciphertext,
+        VortexAead::new(&KEY).encrypt(&NONCE, b"aad", b"through the trait")
+    );
+    let plaintext = Aead::decrypt(
+        &cipher,
+        &nonce,
+        Payload {
+            msg: &ciphertext,
+            aad: b"aad",
+        },
+    )
+    .unwrap();
+    assert_eq!(plaintext, b"through the trait");
+
+    let mut buffer = b"in place".to_vec();
+    AeadInPlace::encrypt_in_place(&cipher, &nonce, b"", &mut buffer).unwrap();
+    buffer[0] ^= 1;
+    assert!(AeadInPlace::decrypt_in_place(&cipher, &nonce, b"", &mut buffer).is_err());
+}
@@ -0,0 +1,194 @@
+// Verified streaming tests
+use proptest::prelude::*;
+use std::io::{ErrorKind, Read};
+use vortex_hash::bao::{self, Decoder, SliceDecoder, HEADER_SIZE, PARENT_SIZE};
+use vortex_hash::tree::CHUNK_SIZE;
+use vortex_hash::TreeHash;
+
+const LENGTHS: [usize; 7] = [
+    0,
+    1,
+    CHUNK_SIZE - 1,
+    CHUNK_SIZE,
+    CHUNK_SIZE + 1,
+    3 * CHUNK_SIZE,
+    5 * CHUNK_SIZE + 3,
+];
+
+fn pattern(length: usize) -> Vec<u8> {
+    (0..length).map(|i| (i % 251) as u8).collect()
+}
+
+fn decode_slice(slice: &[u8], digest: &[u8; 32], start: u64, len: u64) -> std::io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    SliceDecoder::new(slice, digest, start, len).read_to_end(&mut output)?;
+    Ok(output)
+}
+
+#[test]
+fn test_encode_decode_round_trip() {
+    for length in LENGTHS {
+        let data = pattern(length);
+        let (encoded, digest) = bao::encode(&data);
+        assert_eq!(digest, TreeHash::digest(&data), "length {}", length);
+        assert_eq!(encoded.len() as u64, bao::encoded_size(length as u64));
+
+        let mut decoded = Vec::new();
+        Decoder::new(&encoded[..], &digest)
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, data, "length {}", length);
+    }
+}
+
+#[test]
+fn test_corruption_is_detected() {
+    let data = pattern(5 * CHUNK_SIZE + 3);
+    let (encoded, digest) = bao::encode(&data);
+    // Header, root parent node, first chunk, last chunk.
+    for position in [
+        0,
+        7,
+        HEADER_SIZE,
+        HEADER_SIZE + 3 * PARENT_SIZE,
+        encoded.len() - 1,
+    ] {
+        let mut corrupted = encoded.clone();
+        corrupted[position] ^= 0x01;
+        let mut decoded = Vec::new();
+        let result = Decoder::new(&corrupted[..], &digest).read_to_end(&mut decoded);
+        assert!(result.is_err(), "position {}", position);
+    }
+
+    let mut truncated = Vec::new();
+    let error = Decoder::new(&encoded[..encoded.len() - 1], &digest)
+        .read_to_end(&mut truncated)
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_verified_prefix_before_error() {
+    let data = pattern(4 * CHUNK_SIZE);
+    let (mut encoded, digest) = bao::encode(&data);
+    let last = encoded.len() - 1;
+    encoded[last] ^= 0x01;
+
+    let mut decoder = Decoder::new(&encoded[..], &digest);
+    let mut decoded = Vec::new();
+    let mut buf = [0u8; 1000];
+    let error = loop {
+        match decoder.read(&mut buf) {
+            Ok(n) => decoded.extend_from_slice(&buf[..n]),
+            Err(error) => break error,
+        }
+    };
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(decoded, &data[..3 * CHUNK_SIZE]);
+}
+
+#[test]
+fn test_trailing_bytes_rejected() {
+    let data = pattern(2 * CHUNK_SIZE + 5);
+    let (mut encoded, digest) = bao::encode(&data);
+    let slice = bao::extract_slice(&encoded, CHUNK_SIZE as u64, 10).unwrap();
+    encoded.push(0);
+
+    let mut decoder = Decoder::new(&encoded[..], &digest);
+    let mut decoded = Vec::new();
+    let mut buf = [0u8; 1000];
+    let error = loop {
+        match decoder.read(&mut buf) {
+            Ok(n) => decoded.extend_from_slice(&buf[..n]),
+            Err(error) => break error,
+        }
+    };
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(decoded, data);
+
+    let mut padded = slice.clone();
+    padded.extend_from_slice(b"extra");
+    let error = decode_slice(&padded, &digest, CHUNK_SIZE as u64, 10).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert!(decode_slice(&slice, &digest, CHUNK_SIZE as u64, 10).is_ok());
+}
+
+#[test]
+fn test_wrong_digest_rejected() {
+    let (encoded, mut digest) = bao::encode(b"some data");
+    digest[0] ^= 1;
+    let mut decoded = Vec::new();
+    let result = Decoder::new(&encoded[..], &digest).read_to_end(&mut decoded);
+    assert!(result.is_err());
+    assert!(decoded.is_empty());
+}
+
+#[test]
+fn test_slices() {
+    let length = 9 * CHUNK_SIZE + 100;
+    let data = pattern(length);
+    let (encoded, digest) = bao::encode(&data);
+    let chunk = CHUNK_SIZE as u64;
+    let ranges = [
+        (0, 10),
+        (chunk - 1, 2),
+        (4 * chunk, chunk),
+        (3 * chunk + 5, 0),
+        (length as u64 - 1, 100),
+        (length as u64 + 10, 5),
+        (0, length as u64),
+    ];
+    for (start, len) in ranges {
+        let slice = bao::extract_slice(&encoded, start, len).unwrap();
+        let decoded = decode_slice(&slice, &digest, start, len).unwrap();
+        let from = (start as usize).min(length);
+        let to = (start + len).min(length as u64) as usize;
+        assert_eq!(decoded, &data[from..to], "range {}+{}", start, len);
+        if len < chunk {
+            assert!(slice.len() < 3 * CHUNK_SIZE, "range {}+{}", start, len);
+        }
+    }
+}
+
+#[test]
+fn test_tampered_slice_rejected() {
+    let data = pattern(6 * CHUNK_SIZE);
+    let (encoded, digest) = bao::encode(&data);
+    let start = 2 * CHUNK_SIZE as u64 + 10;
+    let slice = bao::extract_slice(&encoded, start, 50).unwrap();
+    for position in [HEADER_SIZE, HEADER_SIZE + PARENT_SIZE + 1, slice.len() - 1] {
+        let mut tampered = slice.clone();
+        tampered[position] ^= 0x80;
+        assert!(decode_slice(&tampered, &digest, start, 50).is_err());
+    }
+    // A slice is only readable for the range it was cut for.
+    assert!(decode_slice(&slice, &digest, 5 * CHUNK_SIZE as u64, 50).is_err());
+}
+
+#[test]
+fn test_extract_slice_rejects_bad_encoding() {
+    let (encoded, _) = bao::encode(&pattern(2 * CHUNK_SIZE));
+    assert!(bao::extract_slice(&encoded[..encoded.len() - 1], 0, 1).is_err());
+    assert!(bao::extract_slice(&encoded[..4], 0, 1).is_err());
+    let mut huge = encoded.clone();
+    huge[..HEADER_SIZE].copy_from_slice(&u64::MAX.to_be_bytes());
+    assert!(bao::extract_slice(&huge, 0, 1).is_err());
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+    #[test]
+    fn prop_slice_round_trip(
+        length in 0usize..6 * CHUNK_SIZE,
+        start in 0u64..7 * CHUNK_SIZE as u64,
+        len in 0u64..3 * CHUNK_SIZE as u64,
+    ) {
+        let data = pattern(length);
+        let (encoded, digest) = bao::encode(&data);
+        let slice = bao::extract_slice(&encoded, start, len).unwrap();
+        let decoded = decode_slice(&slice, &digest, start, len).unwrap();
+        let from = (start as usize).min(length);
+        let to = (start + len).min(length as u64) as usize;
+        prop_assert_eq!(decoded, &data[from..to]);
+    }
+}
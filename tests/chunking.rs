@@ -0,0 +1,147 @@
+// Content-defined chunking tests
+use std::io::{self, Read};
+use vortex_hash::chunking::{Chunk, Chunker, ChunkerParams};
+use vortex_hash::hash;
+
+fn random_data(length: usize, seed: u64) -> Vec<u8> {
+    let mut state = seed;
+    (0..length)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 56) as u8
+        })
+        .collect()
+}
+
+fn chunks(data: &[u8], params: ChunkerParams) -> Vec<Chunk> {
+    Chunker::new(data, params)
+        .unwrap()
+        .collect::<io::Result<_>>()
+        .unwrap()
+}
+
+/// Returns at most a few bytes per call.
+struct Trickle<'a>(&'a [u8], usize);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.1 = self.1 % 7 + 1;
+        let n = self.1.min(buf.len()).min(self.0.len());
+        buf[..n].copy_from_slice(&self.0[..n]);
+        self.0 = &self.0[n..];
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_chunks_cover_input_within_limits() {
+    let data = random_data(1 << 20, 1);
+    let params = ChunkerParams::default();
+    let chunks = chunks(&data, params);
+    let mut offset = 0;
+    for (i, chunk) in chunks.iter().enumerate() {
+        assert_eq!(chunk.offset, offset);
+        assert_eq!(chunk.data, &data[offset as usize..][..chunk.data.len()]);
+        assert_eq!(chunk.fingerprint, hash(&chunk.data));
+        assert!(chunk.data.len() <= params.max_size);
+        if i + 1 < chunks.len() {
+            assert!(chunk.data.len() > params.min_size);
+        }
+        offset += chunk.data.len() as u64;
+    }
+    assert_eq!(offset, data.len() as u64);
+
+    let average = data.len() / chunks.len();
+    assert!(
+        (params.avg_size / 2..2 * params.avg_size).contains(&average),
+        "average chunk size {}",
+        average
+    );
+}
+
+#[test]
+fn test_shift_leaves_later_boundaries_unchanged() {
+    let data = random_data(1 << 20, 2);
+    let mut shifted = vec![0x99];
+    shifted.extend_from_slice(&data);
+    let params = ChunkerParams::default();
+
+    let original: Vec<u64> = chunks(&data, params)
+        .iter()
+        .map(|chunk| chunk.offset + 1)
+        .collect();
+    let moved: Vec<u64> = chunks(&shifted, params)
+        .iter()
+        .map(|chunk| chunk.offset)
+        .collect();
+    let differing = original.iter().filter(|b| !moved.contains(b)).count();
+    assert!(
+        differing <= 3,
+        "{} of {} boundaries moved",
+        differing,
+        original.len()
+    );
+}
+
+#[test]
+fn test_independent_of_read_sizes() {
+    let data = random_data(200_000, 3);
+    let params = ChunkerParams::new(256, 1024, 4096).unwrap();
+    let trickled: Vec<Chunk> = Chunker::new(Trickle(&data, 0), params)
+        .unwrap()
+        .collect::<io::Result<_>>()
+        .unwrap();
+    assert_eq!(trickled, chunks(&data, params));
+}
+
+#[test]
+fn test_small_and_empty_inputs() {
+    let params = ChunkerParams::default();
+    assert!(chunks(b"", params).is_empty());
+    let small = chunks(b"tiny", params);
+    assert_eq!(small.len(), 1);
+    assert_eq!(small[0].data, b"tiny");
+}
+
+#[test]
+fn test_constant_input_is_cut_at_max_size() {
+    let params = ChunkerParams::new(64, 256, 1000).unwrap();
+    let data = vec![0u8; 5000];
+    let sizes: Vec<usize> = chunks(&data, params)
+        .iter()
+        .map(|chunk| chunk.data.len())
+        .collect();
+    assert_eq!(sizes, [1000; 5]);
+}
+
+#[test]
+fn test_params_validation() {
+    assert!(ChunkerParams::default().validate().is_ok());
+    assert!(ChunkerParams::new(1024, 3000, 8192).is_err());
+    assert!(ChunkerParams::new(4096, 4096, 8192).is_err());
+    assert!(ChunkerParams::new(1024, 4096, 4096).is_err());
+    assert!(ChunkerParams::new(16, 4096, 8192).is_err());
+    assert!(ChunkerParams::new(1024, 4096, 1 << 30).is_err());
+
+    // Struct literals skip `ChunkerParams::new`; the chunker checks them.
+    let invalid = ChunkerParams {
+        avg_size: 1000,
+        ..Default::default()
+    };
+    assert!(Chunker::new(&b"data"[..], invalid).is_err());
+}
+
+#[test]
+fn test_read_error_is_returned() {
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+    }
+    let mut chunker = Chunker::new(Failing, ChunkerParams::default()).unwrap();
+    assert!(chunker.next().unwrap().is_err());
+    assert!(chunker.next().is_none());
+}
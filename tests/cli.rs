@@ -0,0 +1,510 @@
+// Command-line tool tests
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use vortex_hash::compatibility::{Algorithm, UniversalHash};
+use vortex_hash::{hash, hash_to, to_hex};
+
+/// Fresh scratch directory for one test.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("vortexhash-cli-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run(dir: &Path, args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_vortexhash"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn test_hashes_files_and_stdin() {
+    let dir = scratch_dir("hash");
+    fs::write(dir.join("a.txt"), b"abc").unwrap();
+    fs::write(dir.join("empty"), b"").unwrap();
+
+    let output = run(&dir, &["a.txt", "empty", "-"], b"from stdin\n");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "{}  a.txt\n{}  empty\n{}  -\n",
+            to_hex(&hash(b"abc")),
+            to_hex(&hash(b"")),
+            to_hex(&hash(b"from stdin\n"))
+        )
+    );
+
+    let output = run(&dir, &[], b"from stdin\n");
+    assert_eq!(
+        stdout(&output),
+        format!("{}  -\n", to_hex(&hash(b"from stdin\n")))
+    );
+}
+
+#[test]
+fn test_large_input_matches_library() {
+    let dir = scratch_dir("large");
+    let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(dir.join("large.bin"), &data).unwrap();
+    let output = run(&dir, &["large.bin"], b"");
+    assert_eq!(
+        stdout(&output),
+        format!("{}  large.bin\n", to_hex(&hash(&data)))
+    );
+}
+
+#[test]
+fn test_tag_and_length() {
+    let dir = scratch_dir("tag");
+    fs::write(dir.join("a.txt"), b"abc").unwrap();
+
+    let output = run(&dir, &["--tag", "a.txt"], b"");
+    assert_eq!(
+        stdout(&output),
+        format!("VORTEXHASH (a.txt) = {}\n", to_hex(&hash(b"abc")))
+    );
+
+    let mut xof = [0u8; 64];
+    hash_to(b"abc", &mut xof);
+    let output = run(&dir, &["--length", "64", "a.txt"], b"");
+    assert_eq!(stdout(&output), format!("{}  a.txt\n", to_hex(&xof)));
+    let output = run(&dir, &["--tag", "-l", "16", "a.txt"], b"");
+    assert_eq!(
+        stdout(&output),
+        format!("VORTEXHASH-XOF-128 (a.txt) = {}\n", to_hex(&xof[..16]))
+    );
+}
+
+#[test]
+fn test_check_reports_each_file() {
+    let dir = scratch_dir("check");
+    fs::write(dir.join("a.txt"), b"abc").unwrap();
+    fs::write(dir.join("b.txt"), b"def").unwrap();
+    let sums = run(&dir, &["a.txt", "b.txt"], b"");
+    fs::write(dir.join("SUMS"), &sums.stdout).unwrap();
+    let tagged = run(&dir, &["--tag", "--length=48", "a.txt"], b"");
+    fs::write(dir.join("TAGGED"), &tagged.stdout).unwrap();
+
+    let output = run(&dir, &["--check", "SUMS", "TAGGED"], b"");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "a.txt: OK\nb.txt: OK\na.txt: OK\n");
+
+    let output = run(&dir, &["-c", "-q", "SUMS"], b"");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "");
+
+    fs::write(dir.join("b.txt"), b"changed").unwrap();
+    let output = run(&dir, &["-c", "SUMS"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "a.txt: OK\nb.txt: FAILED\n");
+    assert!(stderr(&output).contains("1 computed checksum did NOT match"));
+
+    let output = run(&dir, &["-c", "-q", "SUMS"], b"");
+    assert_eq!(stdout(&output), "b.txt: FAILED\n");
+}
+
+#[test]
+fn test_check_reads_sha256sum_style_lines_from_stdin() {
+    let dir = scratch_dir("stdin");
+    fs::write(dir.join("a.txt"), b"abc").unwrap();
+    let digest = to_hex(&hash(b"abc")).to_uppercase();
+    let list = format!("# comment\n{} *a.txt\n\nnot a checksum line\n", digest);
+
+    let output = run(&dir, &["-c"], list.as_bytes());
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "a.txt: OK\n");
+    assert!(stderr(&output).contains("1 line is improperly formatted"));
+
+    let output = run(&dir, &["-c"], b"nothing useful\n");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("no properly formatted checksum lines found"));
+}
+
+#[test]
+fn test_check_missing_file_fails() {
+    let dir = scratch_dir("missing");
+    let list = format!("{}  gone.txt\n", to_hex(&hash(b"")));
+    let output = run(&dir, &["-c"], list.as_bytes());
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "gone.txt: FAILED open or read\n");
+    assert!(stderr(&output).contains("1 listed file could not be read"));
+
+    let output = run(&dir, &["gone.txt"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("gone.txt"));
+}
+
+#[test]
+fn test_escaped_file_names_round_trip() {
+    let dir = scratch_dir("escape");
+    fs::write(dir.join("back\\slash"), b"x").unwrap();
+    fs::write(dir.join("new\nline"), b"y").unwrap();
+
+    let output = run(&dir, &["back\\slash", "new\nline"], b"");
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "\\{}  back\\\\slash\n\\{}  new\\nline\n",
+            to_hex(&hash(b"x")),
+            to_hex(&hash(b"y"))
+        )
+    );
+    fs::write(dir.join("SUMS"), &output.stdout).unwrap();
+    let output = run(&dir, &["-c", "SUMS"], b"");
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "\\back\\\\slash: OK\n\\new\\nline: OK\n");
+}
+
+#[test]
+fn test_usage_errors() {
+    let dir = scratch_dir("usage");
+    for args in [
+        &["--bogus"][..],
+        &["--length"],
+        &["--length", "0"],
+        &["-l", "many"],
+        &["--check=yes"],
+        &["--check", "--tag"],
+    ] {
+        let output = run(&dir, args, b"");
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(stderr(&output).contains("--help"));
+    }
+
+    let output = run(&dir, &["--help"], b"");
+    assert!(output.status.success());
+    assert!(stdout(&output).starts_with("Usage: vortexhash"));
+    let output = run(&dir, &["-V"], b"");
+    assert!(stdout(&output).starts_with("vortexhash "));
+
+    fs::write(dir.join("--tag"), b"abc").unwrap();
+    let output = run(&dir, &["--", "--tag"], b"");
+    assert_eq!(
+        stdout(&output),
+        format!("{}  --tag\n", to_hex(&hash(b"abc")))
+    );
+}
+
+#[test]
+fn test_legacy_algorithms() {
+    let dir = scratch_dir("algo");
+    fs::write(dir.join("a.txt"), b"abc").unwrap();
+
+    let output = run(&dir, &["--algo", "sha256", "a.txt"], b"");
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "{}  a.txt\n",
+            to_hex(&UniversalHash::hash_compatible(b"abc"))
+        )
+    );
+
+    let output = run(&dir, &["--algo=sha1,md5", "a.txt"], b"");
+    assert_eq!(
+        stdout(&output),
+        "SHA1 (a.txt) = a9993e364706816aba3e25717850c26c9cd0d89d\n\
+         MD5 (a.txt) = 900150983cd24fb0d6963f7d28e17f72\n"
+    );
+
+    let output = run(&dir, &["-a", "vortex", "-a", "vortex-256", "a.txt"], b"");
+    assert_eq!(
+        stdout(&output),
+        format!("{}  a.txt\n", to_hex(&hash(b"abc")))
+    );
+
+    // Any registry name or alias is accepted.
+    let output = run(&dir, &["--algo", "SHA-224,vortex-384", "a.txt"], b"");
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "SHA224 (a.txt) = {}\nVORTEXHASH-384 (a.txt) = {}\n",
+            to_hex(&Algorithm::Sha224.digest(b"abc")),
+            to_hex(&Algorithm::Vortex384.digest(b"abc"))
+        )
+    );
+}
+
+#[test]
+fn test_all_algorithms_in_one_pass() {
+    let dir = scratch_dir("algo-all");
+    let output = run(&dir, &["--all"], b"abc");
+    assert!(output.status.success());
+    let text = stdout(&output);
+    let tags: Vec<&str> = text
+        .lines()
+        .map(|line| line.split(" (").next().unwrap())
+        .collect();
+    assert_eq!(
+        tags,
+        [
+            "VORTEXHASH-224",
+            "VORTEXHASH",
+            "VORTEXHASH-384",
+            "VORTEXHASH-512",
+            "SHA224",
+            "SHA256",
+            "SHA384",
+            "SHA512",
+            "SHA1",
+            "MD5",
+            "XXH3",
+            "SIPHASH",
+            "VORTEXHASH-SECURE",
+            "VORTEXHASH-XOF-512"
+        ]
+    );
+    // Registry entries print the digest of `compatibility::Algorithm`.
+    for (line, algorithm) in text.lines().zip(Algorithm::ALL) {
+        assert!(line.ends_with(&format!(") = {}", to_hex(&algorithm.digest(b"abc")))));
+    }
+    assert!(text.contains(&format!("\nVORTEXHASH (-) = {}\n", to_hex(&hash(b"abc")))));
+
+    // Every line of an --all run verifies on its own.
+    fs::write(dir.join("a.txt"), b"abc").unwrap();
+    let output = run(&dir, &["--all", "--length", "16", "a.txt"], b"");
+    assert!(stdout(&output).contains("VORTEXHASH-XOF-128 (a.txt) = "));
+    fs::write(dir.join("SUMS"), &output.stdout).unwrap();
+    let output = run(&dir, &["-c", "SUMS"], b"");
+    assert!(output.status.success(), "{}", stdout(&output));
+    assert_eq!(stdout(&output), "a.txt: OK\n".repeat(14));
+}
+
+#[test]
+fn test_check_legacy_checksums() {
+    let dir = scratch_dir("algo-check");
+    fs::write(dir.join("a.txt"), b"abc").unwrap();
+    let sha256 = to_hex(&UniversalHash::hash_compatible(b"abc"));
+
+    // GNU `sha256sum --tag` output.
+    let output = run(
+        &dir,
+        &["-c"],
+        format!("SHA256 (a.txt) = {}\n", sha256).as_bytes(),
+    );
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "a.txt: OK\n");
+
+    // Plain `sha256sum` output needs the algorithm named.
+    let line = format!("{}  a.txt\n", sha256);
+    let output = run(&dir, &["-c", "--algo", "sha256"], line.as_bytes());
+    assert!(output.status.success());
+    let output = run(&dir, &["-c"], line.as_bytes());
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_algorithm_usage_errors() {
+    let dir = scratch_dir("algo-usage");
+    for args in [
+        &["--algo", "crc32"][..],
+        &["--algo"],
+        &["--all", "--algo", "sha1"],
+        &["--algo", "sha1", "--length", "16"],
+        &["--check", "--all"],
+        &["--all=yes"],
+        &["manifest", "--all"],
+    ] {
+        let output = run(&dir, args, b"");
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+    }
+    let output = run(&dir, &["--algo", "crc32"], b"");
+    assert!(stderr(&output).contains("sha256"));
+}
+
+/// Small tree used by the manifest tests.
+fn manifest_tree(name: &str) -> PathBuf {
+    let dir = scratch_dir(name);
+    fs::create_dir_all(dir.join("tree/src/sub")).unwrap();
+    fs::create_dir_all(dir.join("tree/target")).unwrap();
+    fs::write(dir.join("tree/a.txt"), b"abc").unwrap();
+    fs::write(dir.join("tree/src/main.rs"), b"fn main() {}").unwrap();
+    fs::write(dir.join("tree/src/sub/lib.rs"), b"").unwrap();
+    fs::write(dir.join("tree/target/out.bin"), b"build output").unwrap();
+    fs::write(dir.join("tree/src.txt"), b"sorted before src/").unwrap();
+    dir
+}
+
+#[test]
+fn test_manifest_text() {
+    let dir = manifest_tree("manifest-text");
+    let output = run(&dir, &["manifest", "tree"], b"");
+    assert!(output.status.success(), "{}", stderr(&output));
+    let text = stdout(&output);
+    let lines: Vec<&str> = text.lines().collect();
+    let paths: Vec<&str> = lines[..lines.len() - 1]
+        .iter()
+        .map(|line| line.rsplit("  ").next().unwrap())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "a.txt",
+            "src.txt",
+            "src/main.rs",
+            "src/sub/lib.rs",
+            "target/out.bin"
+        ]
+    );
+    let fields: Vec<&str> = lines[0].split("  ").collect();
+    assert_eq!(fields[0], to_hex(&hash(b"abc")));
+    assert_eq!(fields[1], "3");
+    assert!(fields[2].parse::<i64>().unwrap() > 0);
+    assert!(lines[lines.len() - 1].starts_with("root: "));
+}
+
+#[test]
+fn test_manifest_root_matches_checksum_list() {
+    let dir = manifest_tree("manifest-root");
+    let output = run(&dir, &["manifest", "--root", "tree"], b"");
+    let root = stdout(&output);
+
+    // The root digest is the hash of the sorted `vortexhash` output.
+    let files = [
+        "a.txt",
+        "src.txt",
+        "src/main.rs",
+        "src/sub/lib.rs",
+        "target/out.bin",
+    ];
+    let list = run(&dir.join("tree"), &files, b"");
+    assert_eq!(root, format!("{}  tree\n", to_hex(&hash(&list.stdout))));
+
+    let text = run(&dir, &["manifest", "tree"], b"");
+    assert!(stdout(&text).ends_with(&format!("root: {}\n", &root[..64])));
+
+    // A copy with other timestamps has the same root; any change does not.
+    fs::create_dir_all(dir.join("copy")).unwrap();
+    let copy = run(&dir, &["manifest", "--root", "copy"], b"");
+    assert_ne!(stdout(&copy)[..64], root[..64]);
+    for file in files {
+        let target = dir.join("copy").join(file);
+        fs::create_dir_all(target.parent().unwrap()).unwrap();
+        fs::copy(dir.join("tree").join(file), target).unwrap();
+    }
+    let copy = run(&dir, &["manifest", "--root", "copy"], b"");
+    assert_eq!(stdout(&copy)[..64], root[..64]);
+    fs::write(dir.join("copy/src/sub/lib.rs"), b" ").unwrap();
+    let copy = run(&dir, &["manifest", "--root", "copy"], b"");
+    assert_ne!(stdout(&copy)[..64], root[..64]);
+}
+
+#[test]
+fn test_manifest_include_exclude() {
+    let dir = manifest_tree("manifest-globs");
+    let paths = |args: &[&str]| -> Vec<String> {
+        let mut full = vec!["manifest", "--format", "csv"];
+        full.extend_from_slice(args);
+        full.push("tree");
+        let output = run(&dir, &full, b"");
+        assert!(output.status.success());
+        stdout(&output)
+            .lines()
+            .skip(1)
+            .map(|line| line.split(',').next().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(
+        paths(&["--include", "*.rs"]),
+        ["src/main.rs", "src/sub/lib.rs"]
+    );
+    assert_eq!(paths(&["--include=src/*"]), ["src/main.rs"]);
+    assert_eq!(
+        paths(&["--include", "src/**"]),
+        ["src/main.rs", "src/sub/lib.rs"]
+    );
+    assert_eq!(paths(&["--include", "**/lib.rs"]), ["src/sub/lib.rs"]);
+    assert_eq!(
+        paths(&["--exclude", "target", "--exclude", "s?b"]),
+        ["a.txt", "src.txt", "src/main.rs"]
+    );
+    assert_eq!(
+        paths(&["--include", "*.[a-t]xt", "--exclude", "[!a]*"]),
+        ["a.txt"]
+    );
+}
+
+#[test]
+fn test_manifest_json_and_csv() {
+    let dir = scratch_dir("manifest-formats");
+    fs::create_dir_all(dir.join("tree")).unwrap();
+    fs::write(dir.join("tree/quote\"comma,.txt"), b"q").unwrap();
+
+    let output = run(
+        &dir,
+        &["manifest", "--format", "json", "-l", "16", "tree"],
+        b"",
+    );
+    let json = stdout(&output);
+    let mut digest = [0u8; 16];
+    hash_to(b"q", &mut digest);
+    assert!(json.contains("\"algorithm\": \"VORTEXHASH-XOF-128\""));
+    assert!(json.contains("{\"path\": \"quote\\\"comma,.txt\", \"size\": 1, \"mtime\": "));
+    assert!(json.contains(&format!("\"digest\": \"{}\"}}", to_hex(&digest))));
+
+    let output = run(&dir, &["manifest", "--format", "csv", "tree"], b"");
+    let csv = stdout(&output);
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "path,size,mtime,digest");
+    assert!(lines[1].starts_with("\"quote\"\"comma,.txt\",1,"));
+    assert!(lines[1].ends_with(&to_hex(&hash(b"q"))));
+
+    fs::create_dir_all(dir.join("empty")).unwrap();
+    let output = run(&dir, &["manifest", "--format", "json", "empty"], b"");
+    assert!(stdout(&output).contains("\"files\": []"));
+    assert!(stdout(&output).contains(&to_hex(&hash(b""))));
+}
+
+#[test]
+fn test_manifest_errors() {
+    let dir = manifest_tree("manifest-errors");
+    for args in [
+        &["manifest", "--format", "xml"][..],
+        &["manifest", "tree", "tree"],
+        &["manifest", "--include", "[a-"],
+        &["manifest", "--root=yes"],
+    ] {
+        let output = run(&dir, args, b"");
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+    }
+    let output = run(&dir, &["manifest", "missing"], b"");
+    assert_eq!(output.status.code(), Some(1));
+    let output = run(&dir, &["manifest", "--help"], b"");
+    assert!(stdout(&output).starts_with("Usage: vortexhash manifest"));
+}
+
+#[test]
+fn test_manifest_many_files() {
+    let dir = scratch_dir("manifest-many");
+    fs::create_dir_all(dir.join("tree")).unwrap();
+    for i in 0..200 {
+        fs::write(dir.join(format!("tree/{:03}", i)), i.to_string()).unwrap();
+    }
+    let output = run(&dir, &["manifest", "tree"], b"");
+    let text = stdout(&output);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 201);
+    for (i, line) in lines[..200].iter().enumerate() {
+        assert!(line.starts_with(&to_hex(&hash(i.to_string().as_bytes()))));
+        assert!(line.ends_with(&format!("  {:03}", i)));
+    }
+}
@@ -0,0 +1,112 @@
+// Algorithm registry tests
+use vortex_hash::compatibility::{Algorithm, UniversalHash, REGISTRY};
+use vortex_hash::{hash, to_hex, VortexHash224, VortexHash384, VortexHash512};
+
+/// Digests of `"abc"` from FIPS 180-4 and RFC 1321.
+const ABC_VECTORS: &[(Algorithm, &str)] = &[
+    (
+        Algorithm::Sha224,
+        "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7",
+    ),
+    (
+        Algorithm::Sha256,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+    ),
+    (
+        Algorithm::Sha384,
+        "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed\
+         8086072ba1e7cc2358baeca134c825a7",
+    ),
+    (
+        Algorithm::Sha512,
+        "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+         2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+    ),
+    (Algorithm::Sha1, "a9993e364706816aba3e25717850c26c9cd0d89d"),
+    (Algorithm::Md5, "900150983cd24fb0d6963f7d28e17f72"),
+];
+
+#[test]
+fn test_registry_matches_enum() {
+    assert_eq!(REGISTRY.len(), Algorithm::ALL.len());
+    for (info, algorithm) in REGISTRY.iter().zip(Algorithm::ALL) {
+        assert_eq!(info.algorithm, algorithm);
+        assert_eq!(algorithm.info().name, info.name);
+        assert_eq!(algorithm.digest(b"").len(), info.output_size);
+        assert_eq!(Algorithm::from_multicodec(info.multicodec), Some(algorithm));
+        assert_eq!(algorithm.hasher().algorithm(), algorithm);
+    }
+}
+
+#[test]
+fn test_legacy_vectors() {
+    for &(algorithm, expected) in ABC_VECTORS {
+        assert_eq!(to_hex(&algorithm.digest(b"abc")), expected, "{}", algorithm);
+    }
+    assert_eq!(
+        Algorithm::Sha256.digest(b"abc"),
+        UniversalHash::hash_compatible(b"abc")
+    );
+}
+
+#[test]
+fn test_vortex_entries_match_variants() {
+    let data = b"registry";
+    assert_eq!(
+        Algorithm::Vortex224.digest(data),
+        VortexHash224::digest(data)
+    );
+    assert_eq!(Algorithm::Vortex256.digest(data), hash(data));
+    assert_eq!(
+        Algorithm::Vortex384.digest(data),
+        VortexHash384::digest(data)
+    );
+    assert_eq!(
+        Algorithm::Vortex512.digest(data),
+        VortexHash512::digest(data)
+    );
+}
+
+#[test]
+fn test_parse_names() {
+    assert_eq!("sha256".parse(), Ok(Algorithm::Sha256));
+    assert_eq!("SHA-256".parse(), Ok(Algorithm::Sha256));
+    assert_eq!("vortex-256".parse(), Ok(Algorithm::Vortex256));
+    assert_eq!("vortex".parse(), Ok(Algorithm::Vortex256));
+    assert_eq!("xxh3-64".parse(), Ok(Algorithm::Xxh3));
+    assert!("crc32".parse::<Algorithm>().is_err());
+    assert!("".parse::<Algorithm>().is_err());
+    for algorithm in Algorithm::ALL {
+        assert_eq!(algorithm.to_string().parse(), Ok(algorithm));
+    }
+}
+
+#[test]
+fn test_identifiers() {
+    assert_eq!(Algorithm::Sha256.oid(), Some("2.16.840.1.101.3.4.2.1"));
+    assert_eq!(Algorithm::Sha256.multicodec(), 0x12);
+    assert_eq!(Algorithm::Vortex256.oid(), None);
+    let mut codes: Vec<u64> = Algorithm::ALL.iter().map(|a| a.multicodec()).collect();
+    codes.sort_unstable();
+    codes.dedup();
+    assert_eq!(codes.len(), Algorithm::ALL.len());
+}
+
+#[test]
+fn test_streaming_from_configuration() {
+    let data = [0x5au8; 1000];
+    for algorithm in Algorithm::ALL {
+        let mut hasher = UniversalHash::from_name(algorithm.name()).unwrap();
+        assert_eq!(hasher.output_size(), algorithm.output_size());
+        for chunk in data.chunks(33) {
+            hasher.update(chunk);
+        }
+        let copy = hasher.clone();
+        let first = hasher.finalize_reset();
+        assert_eq!(first, algorithm.digest(&data), "{}", algorithm);
+        assert_eq!(copy.finalize(), first);
+        hasher.update(b"abc");
+        assert_eq!(hasher.finalize(), algorithm.digest(b"abc"));
+    }
+    assert!(UniversalHash::from_name("whirlpool").is_err());
+}
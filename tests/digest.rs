@@ -0,0 +1,125 @@
+// RustCrypto `digest` trait integration tests
+use digest::{Digest, ExtendableOutput, Mac, Update, XofReader};
+use hkdf::{Hkdf, SimpleHkdf};
+use hmac::{Hmac, SimpleHmac};
+use vortex_hash::{hash, hash_to, VortexHash, VortexHashDigest, VortexMac};
+
+fn generic_digest<D: Digest>(data: &[u8]) -> Vec<u8> {
+    let mut hasher = D::new();
+    hasher.update(data);
+    hasher.finalize().to_vec()
+}
+
+fn generic_mac<M: Mac + digest::KeyInit>(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = <M as Mac>::new_from_slice(key).unwrap();
+    Mac::update(&mut mac, data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[test]
+fn test_digest_matches_hash() {
+    assert_eq!(generic_digest::<VortexHash>(b"digest"), hash(b"digest"));
+    assert_eq!(&VortexHash::digest(b"one shot")[..], &hash(b"one shot")[..]);
+    assert_eq!(<VortexHash as Digest>::output_size(), 32);
+}
+
+#[test]
+fn test_digest_finalize_reset() {
+    let mut hasher = <VortexHash as Digest>::new();
+    Digest::update(&mut hasher, b"first");
+    assert_eq!(&hasher.finalize_reset()[..], &hash(b"first")[..]);
+    Digest::update(&mut hasher, b"second");
+    assert_eq!(&hasher.finalize()[..], &hash(b"second")[..]);
+}
+
+#[test]
+fn test_extendable_output_matches_hash_to() {
+    let mut expected = [0u8; 200];
+    hash_to(b"xof", &mut expected);
+
+    let mut hasher = VortexHash::default();
+    Update::update(&mut hasher, b"xof");
+    let mut reader = ExtendableOutput::finalize_xof(hasher);
+    let mut output = [0u8; 200];
+    XofReader::read(&mut reader, &mut output);
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn test_mac_trait_matches_vortex_mac() {
+    assert_eq!(
+        generic_mac::<VortexMac>(b"key", b"data"),
+        VortexMac::mac(b"key", b"data")
+    );
+
+    let mut mac = <VortexMac as Mac>::new_from_slice(b"key").unwrap();
+    Mac::update(&mut mac, b"data");
+    assert!(mac.verify_slice(&VortexMac::mac(b"key", b"data")).is_ok());
+}
+
+#[test]
+fn test_mac_trait_reset() {
+    let mut mac = <VortexMac as Mac>::new_from_slice(b"key").unwrap();
+    Mac::update(&mut mac, b"discarded");
+    Mac::reset(&mut mac);
+    Mac::update(&mut mac, b"data");
+    assert_eq!(
+        &mac.finalize_reset().into_bytes()[..],
+        &VortexMac::mac(b"key", b"data")[..]
+    );
+    Mac::update(&mut mac, b"again");
+    assert_eq!(
+        Mac::finalize(mac).into_bytes()[..],
+        VortexMac::mac(b"key", b"again")[..]
+    );
+}
+
+#[test]
+fn test_hmac_over_vortex_hash() {
+    let tag = |key: &[u8], data: &[u8]| {
+        let mut mac = <SimpleHmac<VortexHash> as Mac>::new_from_slice(key).unwrap();
+        Mac::update(&mut mac, data);
+        mac.finalize().into_bytes()
+    };
+    assert_eq!(tag(b"key", b"data"), tag(b"key", b"data"));
+    assert_ne!(tag(b"key", b"data"), tag(b"other", b"data"));
+}
+
+#[test]
+fn test_hkdf_over_vortex_hash() {
+    let hk = SimpleHkdf::<VortexHash>::new(Some(b"salt"), b"input key material");
+    let mut okm = [0u8; 42];
+    hk.expand(b"info", &mut okm).unwrap();
+    let mut again = [0u8; 42];
+    hk.expand(b"info", &mut again).unwrap();
+    assert_eq!(okm, again);
+    assert!(hk.expand(b"info", &mut [0u8; 255 * 32 + 1]).is_err());
+}
+
+#[test]
+fn test_block_level_hmac_matches_simple_hmac() {
+    let long_key = [0x0bu8; 100];
+    for (key, data) in [
+        (&b"key"[..], &b"data"[..]),
+        (&long_key[..], &[0x5au8; 200][..]),
+    ] {
+        let mut simple = <SimpleHmac<VortexHash> as Mac>::new_from_slice(key).unwrap();
+        Mac::update(&mut simple, data);
+        let mut mac = <Hmac<VortexHashDigest> as Mac>::new_from_slice(key).unwrap();
+        for chunk in data.chunks(7) {
+            Mac::update(&mut mac, chunk);
+        }
+        assert_eq!(mac.finalize().into_bytes(), simple.finalize().into_bytes());
+    }
+
+    let mut okm = [0u8; 42];
+    Hkdf::<VortexHashDigest>::new(Some(b"salt"), b"input key material")
+        .expand(b"info", &mut okm)
+        .unwrap();
+    let mut simple = [0u8; 42];
+    SimpleHkdf::<VortexHashDigest>::new(Some(b"salt"), b"input key material")
+        .expand(b"info", &mut simple)
+        .unwrap();
+    assert_eq!(okm, simple);
+    assert_eq!(generic_digest::<VortexHashDigest>(b"core"), hash(b"core"));
+}
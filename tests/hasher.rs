@@ -0,0 +1,83 @@
+// std::hash::Hasher / BuildHasher tests
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash, Hasher};
+use vortex_hash::{VortexBuildHasher, VortexFastBuildHasher};
+
+fn hash_one<B: BuildHasher, T: Hash>(builder: &B, value: T) -> u64 {
+    builder.hash_one(value)
+}
+
+#[test]
+fn test_hashmap_round_trip() {
+    let mut map = HashMap::with_hasher(VortexBuildHasher::new());
+    for i in 0..1000u32 {
+        map.insert(i, i * 2);
+    }
+    for i in 0..1000u32 {
+        assert_eq!(map.get(&i), Some(&(i * 2)));
+    }
+
+    let mut set = HashSet::with_hasher(VortexFastBuildHasher);
+    set.insert("alpha");
+    set.insert("beta");
+    assert!(set.contains("alpha"));
+    assert!(!set.contains("gamma"));
+}
+
+#[test]
+fn test_same_builder_is_consistent() {
+    let builder = VortexBuildHasher::new();
+    assert_eq!(hash_one(&builder, "value"), hash_one(&builder, "value"));
+    assert_ne!(hash_one(&builder, "value"), hash_one(&builder, "other"));
+}
+
+#[test]
+fn test_random_keys_differ_between_builders() {
+    let a = VortexBuildHasher::new();
+    let b = VortexBuildHasher::new();
+    assert_ne!(hash_one(&a, 42u64), hash_one(&b, 42u64));
+}
+
+#[test]
+fn test_explicit_keys_are_reproducible() {
+    let a = VortexBuildHasher::with_keys(1, 2);
+    let b = VortexBuildHasher::with_keys(1, 2);
+    let c = VortexBuildHasher::with_keys(2, 1);
+    assert_eq!(hash_one(&a, "value"), hash_one(&b, "value"));
+    assert_ne!(hash_one(&a, "value"), hash_one(&c, "value"));
+}
+
+#[test]
+fn test_fast_variant_is_unkeyed() {
+    assert_eq!(
+        hash_one(&VortexFastBuildHasher, [1u8; 200]),
+        hash_one(&VortexFastBuildHasher, [1u8; 200])
+    );
+    assert_ne!(
+        hash_one(&VortexFastBuildHasher, 1u64),
+        hash_one(&VortexBuildHasher::with_keys(0, 0), 1u64)
+    );
+}
+
+#[test]
+fn test_write_split_invariance() {
+    let builder = VortexBuildHasher::with_keys(7, 9);
+    let data = [0xa5u8; 250];
+    let mut whole = builder.build_hasher();
+    whole.write(&data);
+    let mut split = builder.build_hasher();
+    for chunk in data.chunks(17) {
+        split.write(chunk);
+    }
+    assert_eq!(whole.finish(), split.finish());
+}
+
+#[test]
+fn test_trailing_zero_bytes_do_not_collide() {
+    let builder = VortexBuildHasher::with_keys(3, 4);
+    let mut a = builder.build_hasher();
+    a.write(b"a");
+    let mut b = builder.build_hasher();
+    b.write(b"a\0");
+    assert_ne!(a.finish(), b.finish());
+}
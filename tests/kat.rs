@@ -0,0 +1,100 @@
+// Known-answer tests for the published vectors in `vortex_hash::kat`
+use vortex_hash::kat::{
+    HASH_VECTORS, MAC_VECTORS, PERMUTATION_ZERO_STATE, SECURE_HASH_VECTORS, VARIANT_VECTORS,
+    VERSION, XOF_VECTORS,
+};
+use vortex_hash::{
+    hash, hash_secure, hash_to, to_hex, Permutation, SecurityConfig, VortexHash224, VortexHash256,
+    VortexHash384, VortexHash512, VortexMac, ALGORITHM_VERSION,
+};
+
+#[test]
+fn test_kat_version_matches_algorithm() {
+    assert_eq!(VERSION, ALGORITHM_VERSION);
+}
+
+#[test]
+fn test_permutation_vector() {
+    let mut state = [0u8; 128];
+    Permutation::new(64).permute_bytes(&mut state);
+    assert_eq!(to_hex(&state), PERMUTATION_ZERO_STATE);
+}
+
+#[test]
+fn test_hash_vectors() {
+    for vector in HASH_VECTORS {
+        assert_eq!(
+            to_hex(&hash(vector.input)),
+            vector.digest,
+            "hash mismatch for input {:?}",
+            vector.input
+        );
+    }
+}
+
+#[test]
+fn test_secure_hash_vectors() {
+    for vector in SECURE_HASH_VECTORS {
+        let config = SecurityConfig {
+            rounds: vector.rounds,
+            security_level: vector.security_level,
+            ..SecurityConfig::default()
+        };
+        assert_eq!(
+            to_hex(&hash_secure(vector.input, &config).unwrap()),
+            vector.digest,
+            "hash_secure mismatch for {} rounds at level {}",
+            vector.rounds,
+            vector.security_level
+        );
+    }
+}
+
+#[test]
+fn test_mac_vectors() {
+    for vector in MAC_VECTORS {
+        assert_eq!(
+            to_hex(&{
+                let mut mac = VortexMac::with_customization(vector.key, vector.customization);
+                mac.update(vector.input);
+                mac.finalize()
+            }),
+            vector.tag,
+            "mac mismatch for input {:?}",
+            vector.input
+        );
+    }
+}
+
+#[test]
+fn test_xof_vectors() {
+    for vector in XOF_VECTORS {
+        let mut output = vec![0u8; vector.output.len() / 2];
+        hash_to(vector.input, &mut output);
+        assert_eq!(
+            to_hex(&output),
+            vector.output,
+            "xof mismatch for input {:?}",
+            vector.input
+        );
+    }
+}
+
+#[test]
+fn test_variant_vectors() {
+    for vector in VARIANT_VECTORS {
+        let digest = match vector.bits {
+            224 => VortexHash224::digest(vector.input).to_vec(),
+            256 => VortexHash256::digest(vector.input).to_vec(),
+            384 => VortexHash384::digest(vector.input).to_vec(),
+            512 => VortexHash512::digest(vector.input).to_vec(),
+            bits => panic!("no variant with {} bits", bits),
+        };
+        assert_eq!(
+            to_hex(&digest),
+            vector.digest,
+            "{}-bit mismatch",
+            vector.bits
+        );
+    }
+}
@@ -0,0 +1,130 @@
+// Key derivation tests, including the RFC 5869 vectors for the generic construction
+use hmac::Hmac;
+use sha2::Sha256;
+use vortex_hash::kat::KDF_VECTORS;
+use vortex_hash::kdf::{self, Prk, MAX_OUTPUT_SIZE, PRK_SIZE};
+use vortex_hash::{to_hex, VortexMac};
+
+struct Rfc5869Vector {
+    ikm: &'static [u8],
+    salt: &'static [u8],
+    info: &'static [u8],
+    prk: &'static str,
+    okm: &'static str,
+}
+
+// RFC 5869, Appendix A, test cases 1-3 (HMAC-SHA-256)
+const RFC5869_VECTORS: &[Rfc5869Vector] = &[
+    Rfc5869Vector {
+        ikm: &[0x0b; 22],
+        salt: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c,
+        ],
+        info: &[0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9],
+        prk: "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+        okm: "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+    },
+    Rfc5869Vector {
+        ikm: &RANGE_00_4F,
+        salt: &RANGE_60_AF,
+        info: &RANGE_B0_FF,
+        prk: "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+        okm: concat!(
+            "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c",
+            "59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71",
+            "cc30c58179ec3e87c14c01d5c1f3434f1d87",
+        ),
+    },
+    Rfc5869Vector {
+        ikm: &[0x0b; 22],
+        salt: b"",
+        info: b"",
+        prk: "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+        okm: "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8",
+    },
+];
+
+const RANGE_00_4F: [u8; 80] = byte_range(0x00);
+const RANGE_60_AF: [u8; 80] = byte_range(0x60);
+const RANGE_B0_FF: [u8; 80] = byte_range(0xb0);
+
+const fn byte_range(start: u8) -> [u8; 80] {
+    let mut out = [0u8; 80];
+    let mut i = 0;
+    while i < 80 {
+        out[i] = start + i as u8;
+        i += 1;
+    }
+    out
+}
+
+#[test]
+fn test_rfc5869_vectors() {
+    for vector in RFC5869_VECTORS {
+        let prk = kdf::extract_with::<Hmac<Sha256>>(vector.salt, vector.ikm);
+        assert_eq!(to_hex(prk.as_bytes()), vector.prk);
+
+        let mut okm = vec![0u8; vector.okm.len() / 2];
+        kdf::expand_with::<Hmac<Sha256>>(&prk, vector.info, &mut okm).unwrap();
+        assert_eq!(to_hex(&okm), vector.okm);
+    }
+}
+
+#[test]
+fn test_kdf_vectors() {
+    for vector in KDF_VECTORS {
+        let mut okm = vec![0u8; vector.okm.len() / 2];
+        kdf::derive(vector.salt, vector.ikm, vector.info, &mut okm).unwrap();
+        assert_eq!(
+            to_hex(&okm),
+            vector.okm,
+            "kdf mismatch for ikm {:?}",
+            vector.ikm
+        );
+    }
+}
+
+#[test]
+fn test_extract_is_vortex_mac() {
+    let prk = kdf::extract(b"salt", b"ikm");
+    assert_eq!(prk.as_bytes(), &VortexMac::mac(b"salt", b"ikm"));
+}
+
+#[test]
+fn test_output_prefix_consistency() {
+    let prk = kdf::extract(b"salt", b"ikm");
+    let mut short = [0u8; 40];
+    let mut long = [0u8; 100];
+    kdf::expand(&prk, b"info", &mut short).unwrap();
+    kdf::expand(&prk, b"info", &mut long).unwrap();
+    assert_eq!(short[..], long[..40]);
+}
+
+#[test]
+fn test_info_separates_outputs() {
+    let prk = kdf::extract(b"salt", b"ikm");
+    let mut a = [0u8; 32];
+    let mut b = [0u8; 32];
+    kdf::expand(&prk, b"encryption", &mut a).unwrap();
+    kdf::expand(&prk, b"authentication", &mut b).unwrap();
+    assert_ne!(a, b);
+}
+
+#[test]
+fn test_length_limits() {
+    let prk = kdf::extract(b"", b"ikm");
+    let mut okm = vec![0u8; MAX_OUTPUT_SIZE];
+    assert!(kdf::expand(&prk, b"", &mut okm).is_ok());
+    let mut okm = vec![0u8; MAX_OUTPUT_SIZE + 1];
+    assert!(kdf::expand(&prk, b"", &mut okm).is_err());
+    assert!(kdf::expand(&prk, b"", &mut []).is_ok());
+}
+
+#[test]
+fn test_prk_from_bytes() {
+    assert!(Prk::from_bytes(&[0u8; PRK_SIZE - 1]).is_err());
+    assert!(Prk::from_bytes(&[0u8; PRK_SIZE + 1]).is_err());
+    let prk = Prk::from_bytes(&[7u8; PRK_SIZE]).unwrap();
+    assert_eq!(prk.as_bytes(), &[7u8; PRK_SIZE]);
+    assert_eq!(format!("{:?}", prk), "Prk(..)");
+}
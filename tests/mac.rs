@@ -0,0 +1,93 @@
+// Keyed MAC tests
+use vortex_hash::mac::{MAX_TAG_SIZE, MIN_TAG_SIZE};
+use vortex_hash::VortexMac;
+
+#[test]
+fn test_key_length_is_framed() {
+    assert_ne!(VortexMac::mac(b"ab", b"c"), VortexMac::mac(b"a", b"bc"));
+    assert_ne!(VortexMac::mac(b"", b"key"), VortexMac::mac(b"key", b""));
+}
+
+#[test]
+fn test_streaming_matches_one_shot() {
+    let data = [0x33u8; 500];
+    let mut mac = VortexMac::new(b"streaming key");
+    for chunk in data.chunks(13) {
+        mac.update(chunk);
+    }
+    assert_eq!(mac.finalize(), VortexMac::mac(b"streaming key", &data));
+}
+
+#[test]
+fn test_verify_accepts_valid_and_rejects_tampered() {
+    let tag = VortexMac::mac(b"key", b"message");
+
+    let mut mac = VortexMac::new(b"key");
+    mac.update(b"message");
+    assert!(mac.verify(&tag));
+
+    let mut tampered = tag;
+    tampered[31] ^= 1;
+    let mut mac = VortexMac::new(b"key");
+    mac.update(b"message");
+    assert!(!mac.verify(&tampered));
+
+    let mut mac = VortexMac::new(b"other key");
+    mac.update(b"message");
+    assert!(!mac.verify(&tag));
+}
+
+#[test]
+fn test_truncated_tags() {
+    let mut short = [0u8; 16];
+    let mut mac = VortexMac::new(b"key");
+    mac.update(b"message");
+    mac.finalize_into(&mut short).unwrap();
+
+    // The tag length is bound into the output, so a short tag is not a
+    // prefix of the full one.
+    let full = VortexMac::mac(b"key", b"message");
+    assert_ne!(&full[..16], &short[..]);
+
+    let mut mac = VortexMac::new(b"key");
+    mac.update(b"message");
+    assert!(mac.verify(&short));
+
+    let mut mac = VortexMac::new(b"key");
+    mac.update(b"message");
+    assert!(!mac.verify(&full[..16]));
+}
+
+#[test]
+fn test_tag_length_limits() {
+    let mut too_short = vec![0u8; MIN_TAG_SIZE - 1];
+    assert!(VortexMac::new(b"key")
+        .finalize_into(&mut too_short)
+        .is_err());
+    let mut too_long = vec![0u8; MAX_TAG_SIZE + 1];
+    assert!(VortexMac::new(b"key").finalize_into(&mut too_long).is_err());
+    assert!(!VortexMac::new(b"key").verify(&too_short));
+
+    let mut longest = vec![0u8; MAX_TAG_SIZE];
+    assert!(VortexMac::new(b"key").finalize_into(&mut longest).is_ok());
+}
+
+#[test]
+fn test_customization_separates_tags() {
+    let mut a = VortexMac::with_customization(b"key", b"app A");
+    a.update(b"message");
+    let mut b = VortexMac::with_customization(b"key", b"app B");
+    b.update(b"message");
+    assert_ne!(a.finalize(), b.finalize());
+}
+
+#[cfg(feature = "legacy_api")]
+#[test]
+fn test_legacy_hmac_is_separated_from_mac() {
+    use vortex_hash::VortexHash;
+    assert_eq!(VortexHash::hmac(b"ab", b"c"), VortexHash::hmac(b"a", b"bc"));
+    assert_ne!(
+        VortexHash::hmac(b"key", b"data"),
+        VortexMac::mac(b"key", b"data")
+    );
+}
@@ -0,0 +1,237 @@
+// Merkle tree and proof tests
+use vortex_hash::merkle::{
+    empty_root, leaf_hash, node_hash, ConsistencyProof, InclusionProof, MerkleTree,
+    SparseMerkleTree, SparseProof,
+};
+
+fn log_of(size: u64) -> MerkleTree {
+    let mut log = MerkleTree::new();
+    for i in 0..size {
+        log.push(format!("entry {}", i).as_bytes());
+    }
+    log
+}
+
+#[test]
+fn test_small_tree_shapes() {
+    let leaves: Vec<_> = (0..3).map(|i| leaf_hash(&[i])).collect();
+    let mut log = MerkleTree::new();
+    assert_eq!(log.root(), empty_root());
+    for i in 0..3u8 {
+        log.push(&[i]);
+    }
+    assert_eq!(log.root_at(1).unwrap(), leaves[0]);
+    assert_eq!(log.root_at(2).unwrap(), node_hash(&leaves[0], &leaves[1]));
+    assert_eq!(
+        log.root(),
+        node_hash(&node_hash(&leaves[0], &leaves[1]), &leaves[2])
+    );
+}
+
+/// `MTH` of RFC 6962, recomputed from the leaves.
+fn reference_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    match leaves.len() {
+        0 => empty_root(),
+        1 => leaves[0],
+        n => {
+            let k = 1 << (usize::BITS - 1 - (n - 1).leading_zeros());
+            node_hash(&reference_root(&leaves[..k]), &reference_root(&leaves[k..]))
+        }
+    }
+}
+
+#[test]
+fn test_cached_roots_follow_appends() {
+    let mut log = MerkleTree::new();
+    let mut leaves = Vec::new();
+    for i in 0..70u8 {
+        assert_eq!(log.root(), reference_root(&leaves), "{} leaves", i);
+        leaves.push(leaf_hash(&[i]));
+        log.push(&[i]);
+        assert_eq!(log.root(), reference_root(&leaves), "{} leaves", i + 1);
+    }
+    for size in [0, 1, 31, 32, 33, 64, 69] {
+        assert_eq!(
+            log.root_at(size).unwrap(),
+            reference_root(&leaves[..size as usize])
+        );
+    }
+    let copy = log.clone();
+    log.push(b"appended");
+    assert_ne!(log.root(), copy.root());
+    assert_eq!(log.root_at(70).unwrap(), copy.root());
+}
+
+#[test]
+fn test_leaves_and_nodes_are_domain_separated() {
+    let left = leaf_hash(b"a");
+    let right = leaf_hash(b"b");
+    let mut concatenated = left.to_vec();
+    concatenated.extend_from_slice(&right);
+    assert_ne!(leaf_hash(&concatenated), node_hash(&left, &right));
+    assert_ne!(leaf_hash(b""), empty_root());
+}
+
+#[test]
+fn test_inclusion_proofs() {
+    let log = log_of(13);
+    for size in 1..=13 {
+        let root = log.root_at(size).unwrap();
+        for index in 0..size {
+            let proof = log.inclusion_proof(index, size).unwrap();
+            let entry = format!("entry {}", index);
+            assert!(proof.verify(entry.as_bytes(), &root), "{}/{}", index, size);
+            assert!(!proof.verify(b"forged", &root));
+
+            let decoded = InclusionProof::from_bytes(&proof.to_bytes()).unwrap();
+            assert_eq!(decoded, proof);
+            assert!(decoded.verify(entry.as_bytes(), &root));
+        }
+    }
+    assert!(log.inclusion_proof(13, 13).is_err());
+    assert!(log.inclusion_proof(0, 14).is_err());
+}
+
+#[test]
+fn test_inclusion_proof_rejects_wrong_position() {
+    let log = log_of(8);
+    let root = log.root();
+    let proof = log.inclusion_proof(3, 8).unwrap();
+    let mut bytes = proof.to_bytes();
+    bytes[7] = 2; // Claim index 2
+    let moved = InclusionProof::from_bytes(&bytes).unwrap();
+    assert!(!moved.verify(b"entry 3", &root));
+
+    let mut bytes = proof.to_bytes();
+    bytes[15] = 9; // Claim a larger tree
+    let resized = InclusionProof::from_bytes(&bytes).unwrap();
+    assert!(!resized.verify(b"entry 3", &root));
+}
+
+#[test]
+fn test_consistency_proofs() {
+    let log = log_of(17);
+    for new_size in 0..=17 {
+        let new_root = log.root_at(new_size).unwrap();
+        for old_size in 0..=new_size {
+            let old_root = log.root_at(old_size).unwrap();
+            let proof = log.consistency_proof(old_size, new_size).unwrap();
+            assert!(
+                proof.verify(&old_root, &new_root),
+                "{} -> {}",
+                old_size,
+                new_size
+            );
+
+            let decoded = ConsistencyProof::from_bytes(&proof.to_bytes()).unwrap();
+            assert_eq!(decoded, proof);
+            assert!(decoded.verify(&old_root, &new_root));
+        }
+    }
+    assert!(log.consistency_proof(5, 4).is_err());
+    assert!(log.consistency_proof(1, 18).is_err());
+}
+
+#[test]
+fn test_consistency_proof_detects_rewritten_history() {
+    let log = log_of(10);
+    let mut forked = log_of(6);
+    for i in 6..10 {
+        forked.push(format!("entry {}", i).as_bytes());
+    }
+    let mut rewritten = log_of(5);
+    rewritten.push(b"rewritten");
+    for i in 6..10 {
+        rewritten.push(format!("entry {}", i).as_bytes());
+    }
+
+    let proof = rewritten.consistency_proof(6, 10).unwrap();
+    assert!(!proof.verify(&log.root_at(6).unwrap(), &rewritten.root()));
+    let proof = log.consistency_proof(6, 10).unwrap();
+    assert!(proof.verify(&forked.root_at(6).unwrap(), &forked.root()));
+    assert!(!proof.verify(&log.root_at(6).unwrap(), &rewritten.root()));
+}
+
+#[test]
+fn test_proof_decoding_rejects_bad_lengths() {
+    assert!(InclusionProof::from_bytes(&[0u8; 15]).is_err());
+    assert!(InclusionProof::from_bytes(&[0u8; 16 + 31]).is_err());
+    assert!(ConsistencyProof::from_bytes(&[0u8; 16 + 65 * 32]).is_err());
+    assert!(SparseProof::from_bytes(&[0u8; 31]).is_err());
+    let mut bitmap = [0u8; 32];
+    bitmap[0] = 1;
+    assert!(SparseProof::from_bytes(&bitmap).is_err());
+}
+
+#[test]
+fn test_sparse_tree_proofs() {
+    let mut state = SparseMerkleTree::new();
+    let empty = state.root();
+    assert!(state.prove(b"alice").verify(&empty, b"alice", None));
+
+    for (key, value) in [("alice", "100"), ("bob", "250"), ("carol", "7")] {
+        state.insert(key.as_bytes(), value.as_bytes());
+    }
+    let root = state.root();
+    assert_ne!(root, empty);
+    assert_eq!(state.get(b"bob"), Some(&b"250"[..]));
+
+    for (key, value) in [("alice", "100"), ("bob", "250"), ("carol", "7")] {
+        let proof = state.prove(key.as_bytes());
+        let decoded = SparseProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify(&root, key.as_bytes(), Some(value.as_bytes())));
+        assert!(!decoded.verify(&root, key.as_bytes(), Some(b"0")));
+        assert!(!decoded.verify(&root, key.as_bytes(), None));
+    }
+
+    let absent = state.prove(b"dave");
+    assert!(absent.to_bytes().len() < 32 + 4 * 32);
+    let decoded = SparseProof::from_bytes(&absent.to_bytes()).unwrap();
+    assert!(decoded.verify(&root, b"dave", None));
+    assert!(!decoded.verify(&root, b"dave", Some(b"1")));
+}
+
+#[test]
+fn test_sparse_tree_updates() {
+    let mut state = SparseMerkleTree::new();
+    let empty = state.root();
+    state.insert(b"key", b"old");
+    let before = state.root();
+    assert_eq!(state.insert(b"key", b"new"), Some(b"old".to_vec()));
+    assert_ne!(state.root(), before);
+    assert!(!state
+        .prove(b"key")
+        .verify(&state.root(), b"key", Some(b"old")));
+
+    assert_eq!(state.remove(b"key"), Some(b"new".to_vec()));
+    assert!(state.is_empty());
+    assert_eq!(state.root(), empty);
+}
+
+#[test]
+fn test_sparse_cached_root_follows_writes() {
+    let mut state = SparseMerkleTree::new();
+    let mut expected: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+    for i in 0..20u8 {
+        let key = vec![i % 7];
+        let value = vec![i];
+        state.insert(&key, &value);
+        expected.retain(|(k, _)| *k != key);
+        expected.push((key, value));
+        if i % 3 == 0 {
+            let (key, _) = expected.remove(0);
+            state.remove(&key);
+        }
+
+        let mut rebuilt = SparseMerkleTree::new();
+        for (key, value) in &expected {
+            rebuilt.insert(key, value);
+        }
+        assert_eq!(state.root(), rebuilt.root(), "after write {}", i);
+        assert_eq!(state.root(), state.clone().root());
+    }
+    let root = state.root();
+    assert_eq!(state.remove(b"absent"), None);
+    assert_eq!(state.root(), root);
+}
@@ -0,0 +1,198 @@
+// Versioned digest tests
+use vortex_hash::compatibility::Algorithm;
+use vortex_hash::migration::{DigestParams, VersionedDigest, MAX_ROUNDS, PARAMETERIZED_CODE};
+use vortex_hash::{hash, hash_secure, to_hex, SecurityConfig, ALGORITHM_VERSION};
+
+const SHA256_ABC: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+#[test]
+fn test_sha256_is_a_plain_multihash() {
+    let digest = VersionedDigest::compute(Algorithm::Sha256, b"abc");
+    assert_eq!(to_hex(&digest.to_bytes()), format!("1220{}", SHA256_ABC));
+    assert_eq!(digest.to_string(), format!("$sha256${}", SHA256_ABC));
+    assert_eq!(VersionedDigest::from_bytes(&digest.to_bytes()), Ok(digest));
+}
+
+#[test]
+fn test_round_trip_every_algorithm() {
+    for algorithm in Algorithm::ALL {
+        let digest = VersionedDigest::compute(algorithm, b"stored");
+        assert_eq!(digest.digest().len(), algorithm.output_size());
+        let bytes = digest.to_bytes();
+        assert_eq!(VersionedDigest::from_bytes(&bytes).as_ref(), Ok(&digest));
+        let text = digest.to_string();
+        assert_eq!(text.parse::<VersionedDigest>().as_ref(), Ok(&digest));
+        assert!(digest.verify(b"stored"), "{}", algorithm);
+        assert!(!digest.verify(b"Stored"), "{}", algorithm);
+    }
+    let digest = VersionedDigest::compute(Algorithm::Vortex256, b"stored");
+    assert_eq!(digest.digest(), hash(b"stored"));
+    assert_eq!(digest.version(), Some(ALGORITHM_VERSION));
+    assert_eq!(
+        digest.to_string(),
+        format!("$vortex-256$v=2${}", to_hex(&hash(b"stored")))
+    );
+    assert_eq!(
+        VersionedDigest::compute(Algorithm::Md5, b"").version(),
+        None
+    );
+}
+
+#[test]
+fn test_other_versions_do_not_verify() {
+    let verifies = |text: &str| {
+        text.parse::<VersionedDigest>()
+            .is_ok_and(|digest| digest.verify(b"stored"))
+    };
+    let digest = VersionedDigest::compute(Algorithm::Vortex384, b"stored");
+    let text = digest.to_string();
+    assert!(verifies(&text));
+    for version in ["v=1", "v=3", "v=0"] {
+        assert!(!verifies(&text.replace("v=2", version)), "{}", version);
+    }
+
+    let config = SecurityConfig::default();
+    let text = VersionedDigest::compute_secure(b"stored", &config)
+        .unwrap()
+        .to_string();
+    assert!(verifies(&text));
+    assert!(!verifies(&text.replace("v=2", "v=1")));
+
+    // varint(code), varint(length), varint(version), digest
+    let mut bytes = digest.to_bytes();
+    assert_eq!(bytes[5], 2);
+    bytes[5] = 1;
+    assert_eq!(
+        VersionedDigest::from_bytes(&bytes),
+        Err("Unsupported version")
+    );
+}
+
+#[test]
+fn test_parameters_are_recorded() {
+    let config = SecurityConfig {
+        rounds: 32,
+        security_level: 512,
+        ..SecurityConfig::default()
+    };
+    let digest = VersionedDigest::compute_secure(b"secure", &config).unwrap();
+    assert_eq!(digest.algorithm(), Algorithm::Vortex256);
+    assert_eq!(
+        digest.params(),
+        Some(DigestParams {
+            rounds: 32,
+            security_level: 512
+        })
+    );
+    assert_eq!(digest.digest(), hash_secure(b"secure", &config).unwrap());
+    assert!(digest.verify(b"secure"));
+    assert!(!VersionedDigest::compute(Algorithm::Vortex256, b"secure").verify(b"other"));
+
+    let bytes = digest.to_bytes();
+    // varint(0x300100), varint(36), varint(2), varint(32), varint(512), digest
+    assert_eq!(bytes[..9], [0x80, 0x82, 0xc0, 0x01, 36, 2, 32, 0x80, 0x04]);
+    assert_eq!(VersionedDigest::from_bytes(&bytes).as_ref(), Ok(&digest));
+    let text = digest.to_string();
+    assert!(text.starts_with("$vortex-256$v=2$r=32,l=512$"));
+    assert_eq!(text.parse::<VersionedDigest>(), Ok(digest));
+
+    let invalid = SecurityConfig {
+        security_level: 192,
+        ..SecurityConfig::default()
+    };
+    assert!(VersionedDigest::compute_secure(b"secure", &invalid).is_err());
+    assert_eq!(PARAMETERIZED_CODE, 0x30_0100);
+}
+
+#[test]
+fn test_new_validates() {
+    assert!(VersionedDigest::new(Algorithm::Md5, None, vec![0; 16]).is_ok());
+    assert!(VersionedDigest::new(Algorithm::Md5, None, vec![0; 32]).is_err());
+    let params = DigestParams {
+        rounds: 64,
+        security_level: 256,
+    };
+    assert!(VersionedDigest::new(Algorithm::Vortex256, Some(params), vec![0; 32]).is_ok());
+    assert!(VersionedDigest::new(Algorithm::Sha256, Some(params), vec![0; 32]).is_err());
+    let zero_rounds = DigestParams {
+        rounds: 0,
+        security_level: 256,
+    };
+    assert!(VersionedDigest::new(Algorithm::Vortex256, Some(zero_rounds), vec![0; 32]).is_err());
+    let max_rounds = DigestParams {
+        rounds: MAX_ROUNDS,
+        security_level: 256,
+    };
+    assert!(VersionedDigest::new(Algorithm::Vortex256, Some(max_rounds), vec![0; 32]).is_ok());
+    let too_many = DigestParams {
+        rounds: MAX_ROUNDS + 1,
+        ..max_rounds
+    };
+    assert!(VersionedDigest::new(Algorithm::Vortex256, Some(too_many), vec![0; 32]).is_err());
+    let config = SecurityConfig {
+        rounds: MAX_ROUNDS + 1,
+        ..SecurityConfig::default()
+    };
+    assert!(VersionedDigest::compute_secure(b"", &config).is_err());
+}
+
+#[test]
+fn test_malformed_bytes_are_errors() {
+    let valid = VersionedDigest::compute(Algorithm::Sha1, b"abc").to_bytes();
+    let mut trailing = valid.clone();
+    trailing.push(0);
+    let mut unknown = valid.clone();
+    unknown[0] = 0x7f;
+    let mut wrong_length = valid.clone();
+    wrong_length[1] = 21;
+    for bytes in [
+        &[][..],
+        &[0x12],
+        &[0x80],
+        &[0xff; 10],
+        &[0x92, 0x00, 0x00],
+        &valid[..valid.len() - 1],
+        &trailing,
+        &unknown,
+        &wrong_length,
+        &[0x80, 0x82, 0xc0, 0x01, 2, 0x80, 0x04],
+        &[0x80, 0x82, 0xc0, 0x01, 3, 2, 0x80, 0x04],
+        &[0x81, 0x80, 0xc0, 0x01, 0],
+    ] {
+        assert!(VersionedDigest::from_bytes(bytes).is_err(), "{:?}", bytes);
+    }
+}
+
+#[test]
+fn test_malformed_strings_are_errors() {
+    let hex = "00".repeat(32);
+    for text in [
+        String::new(),
+        "sha256".to_string(),
+        format!("sha256${}", hex),
+        format!("$sha256${}$", hex),
+        format!("$crc32${}", hex),
+        format!("$SHA-256${}", hex),
+        format!("$sha256${}", hex.to_uppercase().replace('0', "A")),
+        format!("$sha256${}", &hex[1..]),
+        format!("$sha256${}0", hex),
+        format!("$sha256$r=64,l=256${}", hex),
+        format!("$sha256$v=2${}", hex),
+        format!("$vortex-256${}", hex),
+        format!("$vortex-256$r=64,l=256${}", hex),
+        format!("$vortex-256$v=02${}", hex),
+        format!("$vortex-256$v=2$r=064,l=256${}", hex),
+        format!("$vortex-256$v=2$r=64${}", hex),
+        format!("$vortex-256$v=2$l=256,r=64${}", hex),
+        format!("$vortex-256$v=2$r=99999999999,l=256${}", hex),
+        format!("$vortex-256$v=2$r=64,l=100${}", hex),
+        format!("$vortex-256$v=2$r=4294967295,l=256${}", hex),
+        format!("$vortex-256$v=2$r=1025,l=256${}", hex),
+        format!("$vortex-256$v=2$r=64,l=256${}$", hex),
+    ] {
+        assert!(text.parse::<VersionedDigest>().is_err(), "{}", text);
+    }
+    assert!(format!("$vortex-256$v=2$r=64,l=256${}", hex)
+        .parse::<VersionedDigest>()
+        .is_ok());
+}
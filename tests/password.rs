@@ -0,0 +1,102 @@
+// Password hashing tests: PHC encoding, verification and parameter handling
+use vortex_hash::kat::PASSWORD_VECTORS;
+use vortex_hash::password::{self, Params, OUTPUT_SIZE, SALT_SIZE};
+use vortex_hash::to_hex;
+
+fn cheap() -> Params {
+    Params::new(16, 1, 1).unwrap()
+}
+
+#[test]
+fn test_password_vectors() {
+    for vector in PASSWORD_VECTORS {
+        let params = Params::new(vector.m_cost, vector.t_cost, vector.p_cost).unwrap();
+        let mut output = [0u8; OUTPUT_SIZE];
+        password::hash_raw(vector.password, vector.salt, &params, &mut output).unwrap();
+        assert_eq!(to_hex(&output), vector.digest, "params {:?}", params);
+    }
+}
+
+#[test]
+fn test_hash_and_verify() {
+    let phc = password::hash_password(b"correct horse", &cheap()).unwrap();
+    assert!(phc.starts_with("$vortex$v=1$m=16,t=1,p=1$"));
+    assert!(password::verify(b"correct horse", &phc));
+    assert!(!password::verify(b"correct horsf", &phc));
+    assert!(!password::verify(b"", &phc));
+}
+
+#[test]
+fn test_random_salt() {
+    let a = password::hash_password(b"pw", &cheap()).unwrap();
+    let b = password::hash_password(b"pw", &cheap()).unwrap();
+    assert_ne!(a, b);
+    let salt = a.split('$').nth(4).unwrap();
+    assert_eq!(salt.len(), (SALT_SIZE * 4).div_ceil(3));
+}
+
+#[test]
+fn test_phc_format() {
+    let phc = password::hash_password_with_salt(b"password", b"somesalt", &cheap()).unwrap();
+    let fields: Vec<&str> = phc.split('$').collect();
+    assert_eq!(
+        fields[..5],
+        ["", "vortex", "v=1", "m=16,t=1,p=1", "c29tZXNhbHQ"]
+    );
+    assert_eq!(fields[5].len(), 43);
+    assert!(!fields[5].ends_with('='));
+    assert_eq!(password::parse_params(&phc), Ok(cheap()));
+}
+
+#[test]
+fn test_costs_change_output() {
+    let salt = b"somesalt";
+    let base = password::hash_password_with_salt(b"pw", salt, &cheap()).unwrap();
+    for params in [
+        Params::new(24, 1, 1).unwrap(),
+        Params::new(16, 2, 1).unwrap(),
+        Params::new(16, 1, 2).unwrap(),
+    ] {
+        let other = password::hash_password_with_salt(b"pw", salt, &params).unwrap();
+        assert_ne!(base.rsplit('$').next(), other.rsplit('$').next());
+    }
+}
+
+#[test]
+fn test_tampered_strings_rejected() {
+    let phc = password::hash_password_with_salt(b"pw", b"somesalt", &cheap()).unwrap();
+    let tampered = [
+        phc.replace("$vortex$", "$argon2id$"),
+        phc.replace("v=1", "v=2"),
+        phc.replace("t=1", "t=2"),
+        phc.replace("m=16", "m=016"),
+        phc.replace("t=1", "t=+1"),
+        phc.replace("p=1", "p=1,x=3"),
+        phc.replace("c29tZXNhbHQ", "c29tZXNhbHR"),
+        phc.replace("c29tZXNhbHQ", "c29tZXNhbHQ="),
+        format!("{}$", phc),
+        phc[..phc.len() - 1].to_string(),
+        String::new(),
+    ];
+    for candidate in &tampered {
+        assert!(
+            !password::verify(b"pw", candidate),
+            "accepted {}",
+            candidate
+        );
+    }
+}
+
+#[test]
+fn test_invalid_params() {
+    assert!(Params::new(16, 0, 1).is_err());
+    assert!(Params::new(16, 1, 0).is_err());
+    assert!(Params::new(4096, 1, 256).is_err());
+    assert!(Params::new(15, 1, 2).is_err());
+    assert!(Params::new(8, 1, 1).is_ok());
+    assert!(Params::default().validate().is_ok());
+
+    let mut output = [0u8; OUTPUT_SIZE];
+    assert!(password::hash_raw(b"pw", b"short", &cheap(), &mut output).is_err());
+    assert!(password::hash_raw(b"pw", &[0u8; 65], &cheap(), &mut output).is_err());
+}
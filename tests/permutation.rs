@@ -0,0 +1,77 @@
+// Cross-checks of the unrolled and reference permutation implementations
+use proptest::prelude::*;
+use vortex_hash::permutation::{Permutation, LANES, RC, SBOXES};
+
+#[test]
+fn test_round_constants_match_whitepaper() {
+    assert_eq!(RC[0], 0x0000000000000001);
+    assert_eq!(RC[1], 0x0000000000008082);
+    assert_eq!(RC[2], 0x800000000000808A);
+}
+
+#[test]
+fn test_sboxes_are_bijective() {
+    for sbox in SBOXES.iter() {
+        let mut seen = [false; 16];
+        for &value in sbox.iter() {
+            assert!(!seen[value as usize]);
+            seen[value as usize] = true;
+        }
+    }
+}
+
+#[test]
+fn test_zero_rounds_is_identity() {
+    let mut state = [0x0123_4567_89ab_cdefu64; LANES];
+    Permutation::new(0).permute(&mut state);
+    assert_eq!(state, [0x0123_4567_89ab_cdefu64; LANES]);
+}
+
+#[test]
+fn test_not_symmetric_under_rotation() {
+    let permutation = Permutation::new(64);
+    let mut state = [0u64; LANES];
+    state[3] = 1;
+    let mut rotated = state;
+    rotated.rotate_left(4);
+    permutation.permute(&mut state);
+    permutation.permute(&mut rotated);
+    state.rotate_left(4);
+    assert_ne!(state, rotated);
+}
+
+#[test]
+fn test_zero_state_is_not_fixed_point() {
+    let mut state = [0u64; LANES];
+    Permutation::new(1).permute(&mut state);
+    assert_ne!(state, [0u64; LANES]);
+}
+
+#[test]
+fn test_unrolled_matches_reference_all_sboxes() {
+    // 300 rounds cover every S-box and wrap around the round constant table.
+    let permutation = Permutation::new(300);
+    let mut fast = [0u64; LANES];
+    for (i, lane) in fast.iter_mut().enumerate() {
+        *lane = (i as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+    }
+    let mut reference = fast;
+    permutation.permute(&mut fast);
+    permutation.permute_reference(&mut reference);
+    assert_eq!(fast, reference);
+}
+
+proptest! {
+    #[test]
+    fn prop_unrolled_matches_reference(
+        lanes in proptest::array::uniform16(any::<u64>()),
+        rounds in 0u32..24,
+    ) {
+        let permutation = Permutation::new(rounds);
+        let mut fast = lanes;
+        let mut reference = lanes;
+        permutation.permute(&mut fast);
+        permutation.permute_reference(&mut reference);
+        prop_assert_eq!(fast, reference);
+    }
+}
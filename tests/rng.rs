@@ -0,0 +1,110 @@
+// Deterministic random bit generator tests
+use rand::seq::SliceRandom;
+use rand::{Rng, RngCore, SeedableRng};
+use vortex_hash::kat::RNG_VECTORS;
+use vortex_hash::rng::{VortexRng, BLOCK_SIZE};
+use vortex_hash::to_hex;
+
+fn output(rng: &mut VortexRng, length: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; length];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
+
+#[test]
+fn test_rng_vectors() {
+    for vector in RNG_VECTORS {
+        let mut rng = VortexRng::from_seed(*vector.seed);
+        assert_eq!(to_hex(&output(&mut rng, 64)), vector.output);
+    }
+}
+
+#[test]
+fn test_deterministic_given_seed() {
+    let mut a = VortexRng::from_seed([1u8; 32]);
+    let mut b = VortexRng::from_seed([1u8; 32]);
+    assert_eq!(output(&mut a, 1000), output(&mut b, 1000));
+    assert_eq!(a.next_u64(), b.next_u64());
+
+    let mut c = VortexRng::from_seed([2u8; 32]);
+    let mut a = VortexRng::from_seed([1u8; 32]);
+    assert_ne!(output(&mut a, 32), output(&mut c, 32));
+
+    let mut d = VortexRng::seed_from_u64(42);
+    let mut e = VortexRng::seed_from_u64(42);
+    let mut deck: Vec<u32> = (0..52).collect();
+    let mut other = deck.clone();
+    deck.shuffle(&mut d);
+    other.shuffle(&mut e);
+    assert_eq!(deck, other);
+    assert_eq!(d.gen_range(0..1_000_000), e.gen_range(0..1_000_000));
+}
+
+#[test]
+fn test_output_independent_of_call_pattern() {
+    let mut whole = VortexRng::from_seed([3u8; 32]);
+    let expected = output(&mut whole, 3 * BLOCK_SIZE);
+
+    let mut pieces = VortexRng::from_seed([3u8; 32]);
+    let mut actual = Vec::new();
+    for length in [1, 7, 200, 64, 333].iter().cycle() {
+        if actual.len() >= expected.len() {
+            break;
+        }
+        actual.extend(output(&mut pieces, *length));
+    }
+    assert_eq!(&actual[..expected.len()], &expected[..]);
+
+    let mut words = VortexRng::from_seed([3u8; 32]);
+    assert_eq!(words.next_u32().to_le_bytes(), expected[..4]);
+    assert_eq!(words.next_u64().to_le_bytes(), expected[4..12]);
+}
+
+#[test]
+fn test_output_differs_after_reseed() {
+    let mut plain = VortexRng::from_seed([4u8; 32]);
+    let mut reseeded = VortexRng::from_seed([4u8; 32]);
+    reseeded.reseed(b"fresh entropy");
+    assert_ne!(output(&mut plain, 64), output(&mut reseeded, 64));
+
+    // Reseeding discards buffered output and depends on the entropy.
+    let mut a = VortexRng::from_seed([4u8; 32]);
+    let mut b = VortexRng::from_seed([4u8; 32]);
+    let mut c = VortexRng::from_seed([4u8; 32]);
+    let _ = (output(&mut a, 10), output(&mut b, 10), output(&mut c, 10));
+    let next = output(&mut VortexRng::from_seed([4u8; 32]), 20)[10..].to_vec();
+    a.reseed(b"x");
+    b.reseed(b"x");
+    c.reseed(b"y");
+    let (a, b, c) = (output(&mut a, 10), output(&mut b, 10), output(&mut c, 10));
+    assert_eq!(a, b);
+    assert_ne!(a, c);
+    assert_ne!(a, next);
+
+    let mut os = VortexRng::from_seed([4u8; 32]);
+    os.reseed_from_os();
+    assert_ne!(
+        output(&mut os, 64),
+        output(&mut VortexRng::from_seed([4u8; 32]), 64)
+    );
+}
+
+#[test]
+fn test_os_seeded_generators_differ() {
+    let mut a = VortexRng::new();
+    let mut b = VortexRng::default();
+    assert_ne!(output(&mut a, 32), output(&mut b, 32));
+    assert_eq!(format!("{:?}", a), "VortexRng(..)");
+}
+
+#[test]
+fn test_output_statistics() {
+    let mut rng = VortexRng::from_seed([5u8; 32]);
+    let bytes = output(&mut rng, 64 * 1024);
+    let ones: u32 = bytes.iter().map(|b| b.count_ones()).sum();
+    let bits = bytes.len() as f64 * 8.0;
+    assert!((ones as f64 / bits - 0.5).abs() < 0.01);
+
+    let blocks: std::collections::HashSet<&[u8]> = bytes.chunks(32).collect();
+    assert_eq!(blocks.len(), bytes.len() / 32);
+}
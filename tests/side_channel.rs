@@ -9,11 +9,11 @@ fn test_timing_variation() {
     let data2 = b"test data 2";
 
     let start1 = Instant::now();
-    let _hash1 = VortexHash::hash_secure(data1, &SecurityConfig::default());
+    let _hash1 = VortexHash::hash_secure(data1, &SecurityConfig::default()).unwrap();
     let duration1 = start1.elapsed();
 
     let start2 = Instant::now();
-    let _hash2 = VortexHash::hash_secure(data2, &SecurityConfig::default());
+    let _hash2 = VortexHash::hash_secure(data2, &SecurityConfig::default()).unwrap();
     let duration2 = start2.elapsed();
 
     // Check for significant timing differences (should be minimal with constant-time)
@@ -74,7 +74,7 @@ fn test_cache_access_pattern() {
     let data = vec![0u8; 4096]; // Cache line size multiple
 
     let start = Instant::now();
-    let hash = VortexHash::hash_secure(&data, &SecurityConfig::default());
+    let hash = VortexHash::hash_secure(&data, &SecurityConfig::default()).unwrap();
     let duration = start.elapsed();
 
     // Verify uniform cache access (basic check)
@@ -94,7 +94,7 @@ fn test_valgrind_cachegrind() {
     // This would be run under valgrind --tool=cachegrind
     // For now, stub to ensure constant-time properties
     let data = b"valgrind test";
-    let hash = VortexHash::hash_secure(data, &SecurityConfig::default());
+    let hash = VortexHash::hash_secure(data, &SecurityConfig::default()).unwrap();
 
     // Basic assertion
     assert_eq!(hash.len(), 32);
@@ -108,8 +108,8 @@ fn test_power_analysis_resistance() {
     let data1 = b"power test 1";
     let data2 = b"power test 2";
 
-    let hash1 = VortexHash::hash_secure(data1, &SecurityConfig::default());
-    let hash2 = VortexHash::hash_secure(data2, &SecurityConfig::default());
+    let hash1 = VortexHash::hash_secure(data1, &SecurityConfig::default()).unwrap();
+    let hash2 = VortexHash::hash_secure(data2, &SecurityConfig::default()).unwrap();
 
     // Basic check for consistent output
     assert_eq!(hash1.len(), 32);
@@ -120,7 +120,7 @@ fn test_power_analysis_resistance() {
 #[test]
 fn test_electromagnetic_leakage() {
     let data = b"em leakage test";
-    let hash = VortexHash::hash_secure(data, &SecurityConfig::default());
+    let hash = VortexHash::hash_secure(data, &SecurityConfig::default()).unwrap();
 
     // Verify hash is computed
     assert_eq!(hash.len(), 32);
@@ -130,7 +130,7 @@ fn test_electromagnetic_leakage() {
 fn test_timing_attack_mitigation() {
     let data = b"timing attack";
     let start = Instant::now();
-    let hash = VortexHash::hash_secure(data, &SecurityConfig::default());
+    let hash = VortexHash::hash_secure(data, &SecurityConfig::default()).unwrap();
     let duration = start.elapsed();
 
     // Check that hashing completes within reasonable time
@@ -141,7 +141,7 @@ fn test_timing_attack_mitigation() {
 #[test]
 fn test_side_channel_entropy() {
     let data = b"entropy test";
-    let hash = VortexHash::hash_secure(data, &SecurityConfig::default());
+    let hash = VortexHash::hash_secure(data, &SecurityConfig::default()).unwrap();
 
     // Basic entropy check (non-zero hash)
     assert!(!hash.iter().all(|&b| b == 0));
@@ -153,11 +153,11 @@ fn test_constant_time_hashing() {
     let data2 = b"constant time 2";
 
     let start1 = Instant::now();
-    let _hash1 = VortexHash::hash_secure(data1, &SecurityConfig::default());
+    let _hash1 = VortexHash::hash_secure(data1, &SecurityConfig::default()).unwrap();
     let duration1 = start1.elapsed();
 
     let start2 = Instant::now();
-    let _hash2 = VortexHash::hash_secure(data2, &SecurityConfig::default());
+    let _hash2 = VortexHash::hash_secure(data2, &SecurityConfig::default()).unwrap();
     let duration2 = start2.elapsed();
 
     // Check timing is similar
@@ -168,7 +168,7 @@ fn test_constant_time_hashing() {
 #[test]
 fn test_side_channel_isolation() {
     let data = b"isolation test";
-    let hash = VortexHash::hash_secure(data, &SecurityConfig::default());
+    let hash = VortexHash::hash_secure(data, &SecurityConfig::default()).unwrap();
 
     // Ensure no side effects
     assert_eq!(hash.len(), 32);
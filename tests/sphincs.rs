@@ -0,0 +1,110 @@
+// Stateless hash-based signature tests
+#![cfg(feature = "quantum")]
+
+use std::sync::OnceLock;
+use vortex_hash::sphincs::{ParameterSet, Signature, SigningKey, VerifyingKey};
+
+const SEED: [u8; 96] = [5u8; 96];
+
+/// Signing is slow, so the tests share one key and one signature.
+fn fixture() -> &'static (SigningKey, Signature) {
+    static FIXTURE: OnceLock<(SigningKey, Signature)> = OnceLock::new();
+    FIXTURE.get_or_init(|| {
+        let signing_key = SigningKey::from_seed(ParameterSet::Fast, &SEED);
+        let signature = signing_key.sign(b"message");
+        (signing_key, signature)
+    })
+}
+
+#[test]
+fn test_sign_verify() {
+    let (signing_key, signature) = fixture();
+    let verifying_key = signing_key.verifying_key();
+    assert_eq!(
+        signature.as_bytes().len(),
+        ParameterSet::Fast.signature_size()
+    );
+    assert!(verifying_key.verify(b"message", signature));
+    assert!(!verifying_key.verify(b"messagf", signature));
+}
+
+#[test]
+fn test_deterministic_signing() {
+    let (signing_key, signature) = fixture();
+    let restored = SigningKey::from_bytes(&signing_key.to_bytes()).unwrap();
+    assert_eq!(&restored.sign(b"message"), signature);
+}
+
+#[test]
+fn test_randomized_signing() {
+    let (signing_key, signature) = fixture();
+    let randomized = signing_key.sign_randomized(b"message");
+    assert_ne!(&randomized, signature);
+    assert!(signing_key.verifying_key().verify(b"message", &randomized));
+}
+
+#[test]
+fn test_tampered_signature_rejected() {
+    let (signing_key, signature) = fixture();
+    let verifying_key = signing_key.verifying_key();
+    let bytes = signature.as_bytes();
+    // Randomizer, FORS part, a middle hypertree layer and the last byte.
+    for position in [0, 40, 11_000, 30_000, bytes.len() - 1] {
+        let mut tampered = bytes.to_vec();
+        tampered[position] ^= 0x01;
+        let tampered = Signature::from_bytes(ParameterSet::Fast, &tampered).unwrap();
+        assert!(!verifying_key.verify(b"message", &tampered), "{}", position);
+    }
+}
+
+#[test]
+fn test_wrong_key_rejected() {
+    let (_, signature) = fixture();
+    let other = SigningKey::from_seed(ParameterSet::Fast, &[6u8; 96]).verifying_key();
+    assert!(!other.verify(b"message", signature));
+}
+
+#[test]
+fn test_serialization() {
+    let (signing_key, signature) = fixture();
+    let verifying_key = signing_key.verifying_key();
+    let bytes = verifying_key.to_bytes();
+    assert_eq!(bytes[0], ParameterSet::Fast.id());
+    assert_eq!(VerifyingKey::from_bytes(&bytes).unwrap(), verifying_key);
+
+    let parsed = Signature::from_bytes(ParameterSet::Fast, signature.as_bytes()).unwrap();
+    assert_eq!(&parsed, signature);
+    assert!(Signature::from_bytes(ParameterSet::Fast, &signature.as_bytes()[1..]).is_err());
+    assert!(Signature::from_bytes(ParameterSet::Small, signature.as_bytes()).is_err());
+
+    let mut bad_set = bytes;
+    bad_set[0] = 9;
+    assert!(VerifyingKey::from_bytes(&bad_set).is_err());
+    assert!(VerifyingKey::from_bytes(&bytes[1..]).is_err());
+    assert!(SigningKey::from_bytes(&signing_key.to_bytes()[1..]).is_err());
+}
+
+#[test]
+fn test_parameter_sets() {
+    assert_eq!(ParameterSet::Small.signature_size(), 29_792);
+    assert_eq!(ParameterSet::Fast.signature_size(), 49_856);
+    for set in [ParameterSet::Small, ParameterSet::Fast] {
+        assert_eq!(ParameterSet::from_id(set.id()), Ok(set));
+    }
+}
+
+// The "small" set takes minutes to sign in unoptimized builds; run with
+// `cargo test --release --features quantum -- --ignored`.
+#[test]
+#[ignore]
+fn test_small_sign_verify() {
+    let signing_key = SigningKey::from_seed(ParameterSet::Small, &SEED);
+    let verifying_key = signing_key.verifying_key();
+    let signature = signing_key.sign(b"message");
+    assert_eq!(
+        signature.as_bytes().len(),
+        ParameterSet::Small.signature_size()
+    );
+    assert!(verifying_key.verify(b"message", &signature));
+    assert!(!verifying_key.verify(b"other", &signature));
+}
@@ -0,0 +1,81 @@
+// Incremental hashing tests
+use proptest::prelude::*;
+use vortex_hash::{hash, hash_secure, SecurityConfig, VortexHash};
+
+fn hash_in_pieces(config: &SecurityConfig, data: &[u8], splits: &[usize]) -> [u8; 32] {
+    let mut hasher = VortexHash::new(config).unwrap();
+    let mut rest = data;
+    for &split in splits {
+        let (head, tail) = rest.split_at(split.min(rest.len()));
+        hasher.update(head);
+        rest = tail;
+    }
+    hasher.update(rest);
+    hasher.finalize()
+}
+
+#[test]
+fn test_absorb_carries_partial_blocks() {
+    let mut split = VortexHash::default();
+    split.absorb(b"ab");
+    split.absorb(b"c");
+    let mut whole = VortexHash::default();
+    whole.absorb(b"abc");
+    assert_eq!(split.finalize(), whole.finalize());
+}
+
+#[test]
+fn test_finalize_reset_reuses_hasher() {
+    let mut hasher = VortexHash::default();
+    hasher.update(b"first");
+    assert_eq!(hasher.finalize_reset(), hash(b"first"));
+    hasher.update(b"second");
+    assert_eq!(hasher.finalize_reset(), hash(b"second"));
+}
+
+#[test]
+fn test_reset_discards_input() {
+    let mut hasher = VortexHash::default();
+    hasher.update(&[0xffu8; 200]);
+    hasher.reset();
+    hasher.update(b"after reset");
+    assert_eq!(hasher.finalize(), hash(b"after reset"));
+}
+
+#[test]
+fn test_reset_keeps_configuration() {
+    let mut config = SecurityConfig::new();
+    config.security_level = 128;
+    let mut hasher = VortexHash::new(&config).unwrap();
+    hasher.update(b"discarded");
+    hasher.reset();
+    hasher.update(b"data");
+    assert_eq!(hasher.finalize(), hash_secure(b"data", &config).unwrap());
+}
+
+#[test]
+fn test_cloned_mid_state_diverges_independently() {
+    let mut hasher = VortexHash::default();
+    hasher.update(b"common prefix ");
+    let mut fork = hasher.clone();
+    hasher.update(b"left");
+    fork.update(b"right");
+    assert_eq!(hasher.finalize(), hash(b"common prefix left"));
+    assert_eq!(fork.finalize(), hash(b"common prefix right"));
+}
+
+proptest! {
+    #[test]
+    fn prop_splits_do_not_change_digest(
+        data in proptest::collection::vec(any::<u8>(), 0..600),
+        splits in proptest::collection::vec(0usize..150, 0..8),
+        level in prop_oneof![Just(128u32), Just(256u32), Just(512u32)],
+    ) {
+        let mut config = SecurityConfig::new();
+        config.security_level = level;
+        prop_assert_eq!(
+            hash_in_pieces(&config, &data, &splits),
+            hash_secure(&data, &config).unwrap()
+        );
+    }
+}
@@ -0,0 +1,89 @@
+// Tree hashing mode tests
+use proptest::prelude::*;
+use vortex_hash::kat::TREE_VECTORS;
+use vortex_hash::tree::CHUNK_SIZE;
+use vortex_hash::{hash, to_hex, TreeHash};
+
+fn pattern(length: usize) -> Vec<u8> {
+    (0..length).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn test_tree_vectors() {
+    for vector in TREE_VECTORS {
+        assert_eq!(
+            to_hex(&TreeHash::digest(&pattern(vector.length))),
+            vector.digest,
+            "tree mismatch for length {}",
+            vector.length
+        );
+    }
+}
+
+#[test]
+fn test_streaming_matches_one_shot() {
+    let data = pattern(40 * CHUNK_SIZE + 123);
+    let expected = TreeHash::digest(&data);
+    for piece in [1000, CHUNK_SIZE, 3 * CHUNK_SIZE + 7, 33 * CHUNK_SIZE] {
+        let mut hasher = TreeHash::new();
+        for chunk in data.chunks(piece) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize(), expected, "piece size {}", piece);
+    }
+}
+
+#[test]
+fn test_independent_of_thread_count() {
+    let data = pattern(100 * CHUNK_SIZE);
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+    let single = pool.install(|| TreeHash::digest(&data));
+    assert_eq!(single, TreeHash::digest(&data));
+}
+
+#[test]
+fn test_domain_separated_from_hash() {
+    assert_ne!(TreeHash::digest(b""), hash(b""));
+    assert_ne!(TreeHash::digest(b"abc"), hash(b"abc"));
+}
+
+#[test]
+fn test_length_and_order_are_bound() {
+    let zeros = vec![0u8; 2 * CHUNK_SIZE + 1];
+    for length in [0, 1, CHUNK_SIZE - 1, CHUNK_SIZE, 2 * CHUNK_SIZE] {
+        assert_ne!(
+            TreeHash::digest(&zeros[..length]),
+            TreeHash::digest(&zeros[..length + 1]),
+            "length {}",
+            length
+        );
+    }
+
+    let data = pattern(2 * CHUNK_SIZE);
+    let mut swapped = data[CHUNK_SIZE..].to_vec();
+    swapped.extend_from_slice(&data[..CHUNK_SIZE]);
+    assert_ne!(TreeHash::digest(&data), TreeHash::digest(&swapped));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+    #[test]
+    fn prop_splits_do_not_change_digest(
+        length in 0usize..5 * CHUNK_SIZE,
+        splits in proptest::collection::vec(0usize..2 * CHUNK_SIZE, 0..6),
+    ) {
+        let data = pattern(length);
+        let mut hasher = TreeHash::new();
+        let mut rest = &data[..];
+        for split in splits {
+            let (head, tail) = rest.split_at(split.min(rest.len()));
+            hasher.update(head);
+            rest = tail;
+        }
+        hasher.update(rest);
+        prop_assert_eq!(hasher.finalize(), TreeHash::digest(&data));
+    }
+}
@@ -0,0 +1,40 @@
+// Fixed-length variant tests
+use vortex_hash::{hash, VortexHash224, VortexHash256, VortexHash384, VortexHash512};
+
+#[test]
+fn test_output_sizes() {
+    assert_eq!(VortexHash224::OUTPUT_SIZE, 28);
+    assert_eq!(VortexHash256::OUTPUT_SIZE, 32);
+    assert_eq!(VortexHash384::OUTPUT_SIZE, 48);
+    assert_eq!(VortexHash512::OUTPUT_SIZE, 64);
+    assert_eq!(VortexHash224::digest(b"").len(), VortexHash224::OUTPUT_SIZE);
+    assert_eq!(VortexHash512::digest(b"").len(), VortexHash512::OUTPUT_SIZE);
+}
+
+#[test]
+fn test_256_matches_hash() {
+    assert_eq!(VortexHash256::digest(b"variant"), hash(b"variant"));
+}
+
+#[test]
+fn test_truncated_digests_are_not_prefixes() {
+    let data = b"prefix check";
+    let d224 = VortexHash224::digest(data);
+    let d256 = VortexHash256::digest(data);
+    let d384 = VortexHash384::digest(data);
+    let d512 = VortexHash512::digest(data);
+    assert_ne!(&d256[..28], &d224[..]);
+    assert_ne!(&d384[..32], &d256[..]);
+    assert_ne!(&d512[..32], &d256[..]);
+    assert_ne!(&d512[..48], &d384[..]);
+}
+
+#[test]
+fn test_streaming_matches_one_shot() {
+    let data = [0x5au8; 300];
+    let mut hasher = VortexHash512::new();
+    for chunk in data.chunks(7) {
+        hasher.update(chunk);
+    }
+    assert_eq!(hasher.finalize(), VortexHash512::digest(&data));
+}
@@ -0,0 +1,115 @@
+// Hash-based signature tests (WOTS+ and the stateful Merkle scheme)
+#![cfg(feature = "quantum")]
+
+use vortex_hash::wots::{self, WotsKeyPair, WotsPublicKey, WotsSignature};
+use vortex_hash::xmss::{self, Signature, SigningKey, VerifyingKey};
+
+const SEED: [u8; 96] = [7u8; 96];
+
+#[test]
+fn test_wots_sign_verify() {
+    let key_pair = WotsKeyPair::from_seed(&[1u8; 64]);
+    let public_key = key_pair.public_key();
+    let signature = key_pair.sign(b"message");
+    assert!(public_key.verify(b"message", &signature));
+    assert!(!public_key.verify(b"massage", &signature));
+
+    let other = WotsKeyPair::from_seed(&[2u8; 64]).public_key();
+    assert!(!other.verify(b"message", &signature));
+}
+
+#[test]
+fn test_wots_serialization() {
+    let key_pair = WotsKeyPair::generate();
+    let public_key = key_pair.public_key();
+    let signature = key_pair.sign(b"message");
+
+    let signature_bytes = signature.to_bytes();
+    assert_eq!(signature_bytes.len(), wots::SIGNATURE_SIZE);
+    let public_key_bytes = public_key.to_bytes();
+    let public_key = WotsPublicKey::from_bytes(&public_key_bytes).unwrap();
+    let signature = WotsSignature::from_bytes(&signature_bytes).unwrap();
+    assert!(public_key.verify(b"message", &signature));
+
+    assert!(WotsSignature::from_bytes(&signature_bytes[1..]).is_err());
+    assert!(WotsPublicKey::from_bytes(&public_key_bytes[1..]).is_err());
+}
+
+#[test]
+fn test_wots_tampered_signature() {
+    let key_pair = WotsKeyPair::from_seed(&[3u8; 64]);
+    let public_key = key_pair.public_key();
+    let mut bytes = key_pair.sign(b"message").to_bytes();
+    bytes[100] ^= 1;
+    let signature = WotsSignature::from_bytes(&bytes).unwrap();
+    assert!(!public_key.verify(b"message", &signature));
+}
+
+#[test]
+fn test_xmss_sign_until_exhausted() {
+    let mut signing_key = SigningKey::from_seed(&SEED, 2).unwrap();
+    let verifying_key = signing_key.verifying_key();
+    for i in 0..4u32 {
+        let message = [i as u8; 10];
+        let signature = signing_key.sign(&message).unwrap();
+        assert_eq!(signature.index(), i);
+        assert!(verifying_key.verify(&message, &signature));
+        assert!(!verifying_key.verify(b"other", &signature));
+    }
+    assert_eq!(signing_key.remaining(), 0);
+    assert!(signing_key.sign(b"one more").is_err());
+}
+
+#[test]
+fn test_xmss_deterministic_keys() {
+    let a = SigningKey::from_seed(&SEED, 3).unwrap();
+    let b = SigningKey::from_seed(&SEED, 3).unwrap();
+    assert_eq!(a.verifying_key(), b.verifying_key());
+    let c = SigningKey::from_seed(&[8u8; 96], 3).unwrap();
+    assert_ne!(a.verifying_key(), c.verifying_key());
+}
+
+#[test]
+fn test_xmss_serialization_round_trip() {
+    let mut signing_key = SigningKey::generate(3).unwrap();
+    signing_key.sign(b"first").unwrap();
+    let verifying_key = VerifyingKey::from_bytes(&signing_key.verifying_key().to_bytes()).unwrap();
+
+    let mut restored = SigningKey::from_bytes(&signing_key.to_bytes()).unwrap();
+    assert_eq!(restored.next_index(), 1);
+    let signature = restored.sign(b"second").unwrap();
+    assert_eq!(signature.index(), 1);
+
+    let bytes = signature.to_bytes();
+    assert_eq!(bytes.len(), xmss::signature_size(3));
+    let parsed = Signature::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed, signature);
+    assert!(verifying_key.verify(b"second", &parsed));
+}
+
+#[test]
+fn test_xmss_rejects_corrupt_encodings() {
+    let mut signing_key = SigningKey::from_seed(&SEED, 2).unwrap();
+    let verifying_key = signing_key.verifying_key();
+
+    let mut key_bytes = signing_key.to_bytes();
+    key_bytes[10] ^= 1;
+    assert!(SigningKey::from_bytes(&key_bytes).is_err());
+    let mut key_bytes = signing_key.to_bytes();
+    key_bytes[1..5].copy_from_slice(&5u32.to_be_bytes());
+    assert!(SigningKey::from_bytes(&key_bytes).is_err());
+
+    let bytes = signing_key.sign(b"message").unwrap().to_bytes();
+    assert!(Signature::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    for position in [0, 10, 500, bytes.len() - 1] {
+        let mut tampered = bytes.clone();
+        tampered[position] ^= 0x40;
+        let signature = Signature::from_bytes(&tampered).unwrap();
+        assert!(!verifying_key.verify(b"message", &signature));
+    }
+
+    // A signature from a taller tree does not verify under this key.
+    let mut taller = SigningKey::from_seed(&SEED, 3).unwrap();
+    let signature = taller.sign(b"message").unwrap();
+    assert!(!verifying_key.verify(b"message", &signature));
+}
@@ -0,0 +1,60 @@
+// Extendable-output mode tests
+use std::io::Read;
+use vortex_hash::{hash, hash_to, SecurityConfig, VortexHash};
+
+#[test]
+fn test_output_is_independent_of_read_sizes() {
+    let mut whole = [0u8; 1000];
+    let mut hasher = VortexHash::default();
+    hasher.absorb(b"xof test");
+    hasher.finalize_xof().read_exact(&mut whole).unwrap();
+
+    let mut hasher = VortexHash::default();
+    hasher.absorb(b"xof test");
+    let mut reader = hasher.finalize_xof();
+    let mut pieces = Vec::new();
+    for size in [1, 63, 64, 128, 7, 737] {
+        let mut buf = vec![0u8; size];
+        reader.read_exact(&mut buf).unwrap();
+        pieces.extend_from_slice(&buf);
+    }
+    assert_eq!(&pieces[..], &whole[..]);
+}
+
+#[test]
+fn test_shorter_outputs_are_prefixes() {
+    let mut long = [0u8; 128];
+    let mut short = [0u8; 64];
+    hash_to(b"prefix", &mut long);
+    hash_to(b"prefix", &mut short);
+    assert_eq!(&long[..64], &short[..]);
+}
+
+#[test]
+fn test_blocks_differ() {
+    let mut output = [0u8; 256];
+    hash_to(b"blocks", &mut output);
+    assert_ne!(&output[..64], &output[64..128]);
+    assert_ne!(&output[64..128], &output[128..192]);
+}
+
+#[test]
+fn test_xof_is_domain_separated_from_hash() {
+    let mut output = [0u8; 32];
+    hash_to(b"domain", &mut output);
+    assert_ne!(output, hash(b"domain"));
+}
+
+#[test]
+fn test_xof_honours_security_config() {
+    let mut config = SecurityConfig::new();
+    config.security_level = 512;
+    let mut hasher = VortexHash::new(&config).unwrap();
+    hasher.absorb(b"level");
+    let mut high = [0u8; 64];
+    hasher.finalize_xof().squeeze(&mut high);
+
+    let mut default = [0u8; 64];
+    hash_to(b"level", &mut default);
+    assert_ne!(high, default);
+}